@@ -0,0 +1,315 @@
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    collections::BTreeMap,
+    fmt::Write,
+    sync::atomic::{AtomicU64, Ordering},
+    time::{Duration, Instant},
+};
+
+use crate::{
+    input::Input,
+    parse::{parse_lines, Fields},
+    runner::{with_day, DayAction, Part},
+    Solution,
+};
+
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+
+/// Slowdowns smaller than this are timer noise, whatever their percentage.
+const MIN_REGRESSION_MS: f64 = 0.1;
+
+/// The system allocator, counting each allocation. A binary that installs it with
+/// `#[global_allocator]` gets allocation counts in its benchmarks.
+pub struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc_zeroed(layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+/// Allocations so far, or `None` when `CountingAllocator` is not installed.
+fn allocations() -> Option<u64> {
+    let count = ALLOCATIONS.load(Ordering::Relaxed);
+    if count == 0 {
+        None
+    } else {
+        Some(count)
+    }
+}
+
+fn measure<T>(phase: impl FnOnce() -> anyhow::Result<T>) -> anyhow::Result<(T, Sample)> {
+    let allocations_before = allocations();
+    let start = Instant::now();
+    let result = phase()?;
+    let elapsed = start.elapsed();
+    let allocations = allocations().and_then(|after| allocations_before.map(|b| after - b));
+    Ok((
+        result,
+        Sample {
+            elapsed,
+            allocations,
+        },
+    ))
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct Sample {
+    pub elapsed: Duration,
+    pub allocations: Option<u64>,
+}
+
+#[derive(Clone, Debug)]
+pub struct PhaseStats {
+    pub phase: String,
+    pub samples: Vec<Sample>,
+}
+
+fn median<T: Copy + Ord>(mut values: Vec<T>) -> T {
+    values.sort();
+    values[values.len() / 2]
+}
+
+impl PhaseStats {
+    fn new(phase: &str) -> Self {
+        Self {
+            phase: phase.to_owned(),
+            samples: vec![],
+        }
+    }
+
+    pub fn min(&self) -> Duration {
+        self.samples.iter().map(|s| s.elapsed).min().unwrap()
+    }
+
+    pub fn median(&self) -> Duration {
+        median(self.samples.iter().map(|s| s.elapsed).collect())
+    }
+
+    pub fn max(&self) -> Duration {
+        self.samples.iter().map(|s| s.elapsed).max().unwrap()
+    }
+
+    pub fn allocations(&self) -> Option<u64> {
+        self.samples
+            .iter()
+            .map(|s| s.allocations)
+            .collect::<Option<Vec<_>>>()
+            .map(median)
+    }
+}
+
+/// Times parsing and each requested part, `runs` times over.
+pub fn bench<S: Solution>(
+    input: &Input,
+    parts: &[Part],
+    runs: usize,
+) -> anyhow::Result<Vec<PhaseStats>> {
+    let mut phases = vec![PhaseStats::new("parse")];
+    for part in parts.iter() {
+        if *part == Part::One || S::HAS_PART_2 {
+            phases.push(PhaseStats::new(format!("part {part}").as_str()));
+        }
+    }
+    for _ in 0..runs.max(1) {
+        let (puzzle, sample) = measure(|| S::load(input))?;
+        phases[0].samples.push(sample);
+        for phase in phases[1..].iter_mut() {
+            let (_, sample) = if phase.phase == "part 1" {
+                measure(|| puzzle.part1().map(|a| a.to_string()))?
+            } else {
+                measure(|| puzzle.part2().map(|a| a.to_string()))?
+            };
+            phase.samples.push(sample);
+        }
+    }
+    Ok(phases)
+}
+
+struct Bench<'a> {
+    input: &'a Input,
+    parts: &'a [Part],
+    runs: usize,
+}
+
+impl DayAction for Bench<'_> {
+    type Output = anyhow::Result<Vec<PhaseStats>>;
+
+    fn act<S: Solution>(self) -> Self::Output {
+        bench::<S>(self.input, self.parts, self.runs)
+    }
+}
+
+pub struct DayBench {
+    pub day: usize,
+    pub input: String,
+    pub outcome: anyhow::Result<Vec<PhaseStats>>,
+}
+
+impl DayBench {
+    pub fn new(day: usize, filename: &str, parts: &[Part], runs: usize) -> Self {
+        match Input::from_file(filename) {
+            Ok(input) => Self::with_input(day, &input, parts, runs),
+            Err(e) => Self {
+                day,
+                input: filename.to_owned(),
+                outcome: Err(e),
+            },
+        }
+    }
+
+    /// Benchmarks an input that has already been read, such as standard input.
+    pub fn with_input(day: usize, input: &Input, parts: &[Part], runs: usize) -> Self {
+        Self {
+            day,
+            input: input.name().to_owned(),
+            outcome: with_day(day, Bench { input, parts, runs }).and_then(|outcome| outcome),
+        }
+    }
+}
+
+/// Median milliseconds for each day and phase, from an earlier run.
+pub type Baseline = BTreeMap<(usize, String), f64>;
+
+pub fn load_baseline(input: &Input) -> anyhow::Result<Baseline> {
+    let entries = parse_lines(input, |line| {
+        if line.starts_with('#') || line.trim().is_empty() {
+            return Ok(None);
+        }
+        let mut fields = Fields::split(line, &['\t']);
+        let day = fields.parse("a day")?;
+        let (_, phase) = fields.next("a phase")?;
+        let ms = fields.parse("a median in ms")?;
+        fields.end()?;
+        Ok(Some(((day, phase.to_owned()), ms)))
+    })?;
+    Ok(entries.into_iter().flatten().collect())
+}
+
+pub fn baseline(benches: &[DayBench]) -> String {
+    let mut result = String::from("# day\tphase\tmedian ms\n");
+    for bench in benches.iter() {
+        if let Ok(phases) = &bench.outcome {
+            for phase in phases.iter() {
+                writeln!(
+                    result,
+                    "{}\t{}\t{:.6}",
+                    bench.day,
+                    phase.phase,
+                    millis(phase.median())
+                )
+                .unwrap();
+            }
+        }
+    }
+    result
+}
+
+fn millis(d: Duration) -> f64 {
+    d.as_secs_f64() * 1000.0
+}
+
+/// Percent change from `before` to `after`.
+fn change(before: f64, after: f64) -> f64 {
+    if before == 0.0 {
+        0.0
+    } else {
+        (after - before) / before * 100.0
+    }
+}
+
+/// Renders `benches` as a table, comparing medians against `baseline` when given.
+/// Also returns how many phases slowed down by more than `threshold` percent
+/// (and by more than `MIN_REGRESSION_MS`).
+pub fn table(benches: &[DayBench], baseline: Option<&Baseline>, threshold: f64) -> (String, usize) {
+    let mut result = String::new();
+    let mut regressions = 0;
+    writeln!(
+        result,
+        "{:>3}  {:<6}  {:>10}  {:>10}  {:>10}  {:>8}  {:>10}",
+        "Day", "Phase", "min ms", "median ms", "max ms", "allocs", "vs base"
+    )
+    .unwrap();
+    for bench in benches.iter() {
+        match &bench.outcome {
+            Ok(phases) => {
+                for phase in phases.iter() {
+                    let median = millis(phase.median());
+                    let allocs = phase
+                        .allocations()
+                        .map_or("-".to_owned(), |a| a.to_string());
+                    let comparison =
+                        match baseline.and_then(|b| b.get(&(bench.day, phase.phase.clone()))) {
+                            None => "-".to_owned(),
+                            Some(before) => {
+                                let change = change(*before, median);
+                                if change > threshold && median - before > MIN_REGRESSION_MS {
+                                    regressions += 1;
+                                    format!("{change:+.1}% SLOWER")
+                                } else {
+                                    format!("{change:+.1}%")
+                                }
+                            }
+                        };
+                    writeln!(
+                        result,
+                        "{:>3}  {:<6}  {:>10.3}  {median:>10.3}  {:>10.3}  {allocs:>8}  {comparison:>10}",
+                        bench.day,
+                        phase.phase,
+                        millis(phase.min()),
+                        millis(phase.max())
+                    )
+                    .unwrap();
+                }
+            }
+            Err(e) => {
+                writeln!(result, "{:>3}  error ({}): {e}", bench.day, bench.input).unwrap();
+            }
+        }
+    }
+    (result, regressions)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        bench::{load_baseline, DayBench},
+        input::Input,
+        runner::Part,
+    };
+
+    #[test]
+    fn test_bench() {
+        let bench = DayBench::new(6, "ex/day6a.txt", &Part::both(), 3);
+        let phases = bench.outcome.unwrap();
+        let names = phases.iter().map(|p| p.phase.as_str()).collect::<Vec<_>>();
+        assert_eq!(names, vec!["parse", "part 1", "part 2"]);
+        for phase in phases.iter() {
+            assert_eq!(phase.samples.len(), 3);
+            assert!(phase.min() <= phase.median() && phase.median() <= phase.max());
+        }
+    }
+
+    #[test]
+    fn test_load_baseline() {
+        let input = Input::from("# day\tphase\tmedian ms\n16\tpart 1\t12.5\n");
+        let baseline = load_baseline(&input).unwrap();
+        assert_eq!(baseline.get(&(16, "part 1".to_owned())), Some(&12.5));
+        assert!(load_baseline(&Input::from("16\tpart 1\tslow\n")).is_err());
+    }
+}
@@ -0,0 +1,134 @@
+use std::{env, fs};
+
+use advent2022::{
+    bench::{self, CountingAllocator, DayBench},
+    detect::detect,
+    input::Input,
+    report::Format,
+    runner::{default_input, parse_days, DayRun, Part},
+};
+use anyhow::bail;
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+const USAGE: &str =
+    "Usage: advent <day | first-last | all | auto> [--part 1|2] [--format table|json|csv]
+              [--no-detect] [--bench runs [--baseline file] [--save-baseline file] [--threshold percent]]
+              [input file | -]";
+
+const DEFAULT_THRESHOLD: f64 = 10.0;
+
+fn main() -> anyhow::Result<()> {
+    let mut days = None;
+    let mut auto = false;
+    let mut parts = Part::both();
+    let mut format = Format::Table;
+    let mut runs = None;
+    let mut baseline = None;
+    let mut save_baseline = None;
+    let mut threshold = DEFAULT_THRESHOLD;
+    let mut input = None;
+    let mut detect_days = true;
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if arg == "--no-detect" {
+            detect_days = false;
+        } else if arg.starts_with("--") || arg == "-p" || arg == "-f" {
+            let value = match args.next() {
+                Some(value) => value,
+                None => bail!("{USAGE}"),
+            };
+            match arg.as_str() {
+                "--part" | "-p" => parts = vec![value.parse()?],
+                "--format" | "-f" => format = value.parse()?,
+                "--bench" => runs = Some(value.parse::<usize>()?),
+                "--baseline" => baseline = Some(value),
+                "--save-baseline" => save_baseline = Some(value),
+                "--threshold" => threshold = value.parse()?,
+                _ => bail!("{USAGE}"),
+            }
+        } else if days.is_none() && !auto {
+            if arg == "auto" {
+                auto = true;
+            } else {
+                days = Some(parse_days(arg.as_str())?);
+            }
+        } else if input.is_none() {
+            input = Some(arg);
+        } else {
+            bail!("{USAGE}");
+        }
+    }
+
+    // Kept for solving, as standard input can only be read once.
+    let mut detected = None;
+    let days = match (days, auto, &input) {
+        (Some(days), _, _) => days,
+        (None, true, Some(file)) => {
+            let input = Input::from_file(file.as_str())?;
+            match detect(&input).first() {
+                Some(guess) => {
+                    eprintln!("{file} looks like {guess}");
+                    detected = Some(input);
+                    guess.day..=guess.day
+                }
+                None => bail!("{file}: Does not look like any day's input"),
+            }
+        }
+        (None, true, None) => bail!("auto needs an input file"),
+        (None, false, _) => bail!("{USAGE}"),
+    };
+    if input.is_some() && days.start() != days.end() {
+        bail!("An input file can only be given for a single day");
+    }
+    let filename = |day| input.clone().unwrap_or_else(|| default_input(day));
+
+    match runs {
+        None => {
+            if baseline.is_some() || save_baseline.is_some() {
+                bail!("Baselines need --bench");
+            }
+            let runs: Vec<DayRun> = days
+                .map(|day| match &detected {
+                    Some(input) => DayRun::with_input(
+                        day,
+                        input,
+                        &parts,
+                        format.shows_diagnostics(),
+                        detect_days,
+                    ),
+                    None => DayRun::new(
+                        day,
+                        filename(day).as_str(),
+                        &parts,
+                        format.shows_diagnostics(),
+                        detect_days,
+                    ),
+                })
+                .collect();
+            print!("{}", format.render(&runs));
+        }
+        Some(runs) => {
+            let baseline = match baseline {
+                Some(file) => Some(bench::load_baseline(&Input::from_file(file.as_str())?)?),
+                None => None,
+            };
+            let benches: Vec<DayBench> = days
+                .map(|day| match &detected {
+                    Some(input) => DayBench::with_input(day, input, &parts, runs),
+                    None => DayBench::new(day, filename(day).as_str(), &parts, runs),
+                })
+                .collect();
+            let (table, regressions) = bench::table(&benches, baseline.as_ref(), threshold);
+            print!("{table}");
+            if let Some(file) = save_baseline {
+                fs::write(file, bench::baseline(&benches))?;
+            }
+            if regressions > 0 {
+                bail!("{regressions} phase(s) more than {threshold}% slower than the baseline");
+            }
+        }
+    }
+    Ok(())
+}
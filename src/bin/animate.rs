@@ -0,0 +1,54 @@
+use std::env;
+
+use advent2022::{
+    input::Input,
+    render::Palette,
+    runner::default_input,
+    simulation::{simulate_day, FrameExporter},
+};
+use anyhow::bail;
+
+const USAGE: &str =
+    "Usage: animate <day> <frame directory | .png file> [--format txt|ppm|png|svg|apng]
+               [--ticks n] [--every n] [--delay ms] [--cell-size pixels] [--palette file]
+               [input file | -]";
+
+fn main() -> anyhow::Result<()> {
+    let mut exporter = FrameExporter::default();
+    let mut positional = vec![];
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if arg.starts_with("--") {
+            let value = match args.next() {
+                Some(value) => value,
+                None => bail!("{USAGE}"),
+            };
+            match arg.as_str() {
+                "--format" => exporter.format = value.parse()?,
+                "--ticks" => exporter.max_ticks = value.parse()?,
+                "--every" => exporter.every = value.parse()?,
+                "--delay" => exporter.delay_ms = value.parse()?,
+                "--cell-size" => exporter.renderer.cell_size = value.parse()?,
+                "--palette" => {
+                    exporter.renderer.palette =
+                        Palette::from_input(&Input::from_file(value.as_str())?)?
+                }
+                _ => bail!("{USAGE}"),
+            }
+        } else {
+            positional.push(arg);
+        }
+    }
+    if positional.len() < 2 || positional.len() > 3 {
+        bail!("{USAGE}");
+    }
+    let day = positional[0].parse()?;
+    let filename = positional
+        .get(2)
+        .cloned()
+        .unwrap_or_else(|| default_input(day));
+    let mut simulation = simulate_day(day, &Input::from_file(filename.as_str())?)?;
+    let frames = exporter.export(simulation.as_mut(), positional[1].as_str())?;
+    println!("{frames} frames written to {}", positional[1]);
+    Ok(())
+}
@@ -1,29 +1,92 @@
-use advent_code_lib::{all_lines, simpler_main};
+use advent2022::{
+    cli::DayArgs,
+    day1::{CalorieTally, Elves, Inventory},
+    Solution,
+};
+use anyhow::bail;
 
-fn main() -> anyhow::Result<()> {
-    simpler_main(|filename| {
-        let mut elves = vec![];
-        let mut elf = 0;
+const USAGE: &str = "Usage: day1 [--top k] [--stats | --cap calories | --above calories \
+                     | --heaviest | --csv] [input file | -]";
 
-        for line in all_lines(filename)? {
-            let line = line.trim();
-            if line.len() == 0 {
-                elves.push(elf);
-                elf = 0;
-            } else {
-                elf += line.parse::<i64>().unwrap();
-            }
-        }
-        elves.push(elf);
+/// How many elves `--stats` lists unless `--top` says otherwise.
+const TOP_ELVES: usize = 3;
+
+/// The percentile `--stats` reports.
+const PERCENTILE: f64 = 90.0;
 
-        println!("part 1: {}", elves.iter().max().unwrap());
-        println!("part 2: {}", part2(&elves));
-        Ok(())
-    })
+enum Mode {
+    Answers,
+    Stats,
+    Rebalance(i64),
+    Above(i64),
+    Heaviest,
+    Csv,
 }
 
-fn part2(elves: &Vec<i64>) -> i64 {
-    let mut elves = elves.clone();
-    elves.sort_by_key(|k| -k);
-    elves[..3].iter().sum()
+fn main() -> anyhow::Result<()> {
+    let mut mode = Mode::Answers;
+    let mut k = None;
+    let mut args = DayArgs::new(1, USAGE);
+    while let Some(option) = args.option()? {
+        match option.as_str() {
+            "--top" => k = Some(args.value()?),
+            "--stats" => mode = Mode::Stats,
+            "--cap" => mode = Mode::Rebalance(args.value()?),
+            "--above" => mode = Mode::Above(args.value()?),
+            "--heaviest" => mode = Mode::Heaviest,
+            "--csv" => mode = Mode::Csv,
+            _ => return Err(args.usage()),
+        }
+    }
+    let filename = args.filename();
+    let input = args.input()?;
+    match (mode, k) {
+        (Mode::Answers, None) => {
+            let elves = Elves::from_input(&input)?;
+            println!("Part 1: {}", elves.part1()?);
+            println!("Part 2: {}", elves.part2()?);
+        }
+        (Mode::Answers, Some(k)) => {
+            let tally = CalorieTally::from_input(&input, k)?;
+            for (elf, total) in tally.top() {
+                println!("{elf} {total}");
+            }
+            println!("total {}", tally.top_sum());
+        }
+        (Mode::Stats, k) => {
+            let mut tally = CalorieTally::with_totals(k.unwrap_or(TOP_ELVES));
+            tally.add_input(&input)?;
+            let (Some(median), Some(percentile)) = (tally.median(), tally.percentile(PERCENTILE))
+            else {
+                bail!("{filename}: No elves");
+            };
+            let top = tally
+                .top()
+                .iter()
+                .map(|(elf, _)| elf.to_string())
+                .collect::<Vec<_>>();
+            println!("elves: {}", tally.count());
+            println!("mean: {:.1}", tally.mean());
+            println!("median: {median:.1}");
+            println!("{PERCENTILE}th percentile: {percentile}");
+            println!("top elves: {}", top.join(" "));
+        }
+        (Mode::Rebalance(cap), _) => {
+            for transfer in Inventory::from_input(&input)?.rebalance(cap)? {
+                println!("{transfer}");
+            }
+        }
+        (Mode::Above(threshold), _) => {
+            let inventory = Inventory::from_input(&input)?;
+            for elf in inventory.above(threshold) {
+                println!("{elf} {}", inventory.elves()[elf].total());
+            }
+        }
+        (Mode::Heaviest, _) => match Inventory::from_input(&input)?.heaviest_item() {
+            Some((elf, calories)) => println!("elf {elf} carries {calories} calories"),
+            None => bail!("{filename}: No food items"),
+        },
+        (Mode::Csv, _) => print!("{}", Inventory::from_input(&input)?.to_csv()),
+    }
+    Ok(())
 }
@@ -1,50 +1,32 @@
-use advent_code_lib::{all_lines, simpler_main};
-use std::collections::BTreeSet;
+use advent2022::{cli::DayArgs, day3::Rucksacks, Solution};
+
+const USAGE: &str = "Usage: day3 [--repair] [input file | -]";
+
+/// Group size for the repair planner.
+const GROUP_SIZE: usize = 3;
 
 fn main() -> anyhow::Result<()> {
-    simpler_main(|filename| {
-        let mut total1 = 0;
-        let mut total2 = 0;
-        let mut trio = vec![];
-        for line in all_lines(filename)? {
-            total1 += score(char_intersection_1(line.as_str()));
-            trio.push(line);
-            if trio.len() == 3 {
-                total2 += score(char_intersection_2(&trio));
-                trio = vec![];
-            }
+    let mut repair = false;
+    let mut args = DayArgs::new(3, USAGE);
+    while let Some(option) = args.option()? {
+        match option.as_str() {
+            "--repair" => repair = true,
+            _ => return Err(args.usage()),
         }
-        println!("Part 1: {total1}");
-        println!("Part 2: {total2}");
-        Ok(())
-    })
-}
-
-fn score(c: char) -> i64 {
-    match c {
-        'a'..='z' => c as i64 - 'a' as i64 + 1,
-        'A'..='Z' => c as i64 - 'A' as i64 + 27,
-        _ => panic!("oops!"),
     }
-}
-
-fn char_sets_1(line: &str) -> (BTreeSet<char>, BTreeSet<char>) {
-    let mut chars = line.chars();
-    let left = chars.by_ref().take(line.len() / 2).collect();
-    let right = chars.collect();
-    (left, right)
-}
-
-fn char_intersection_1(line: &str) -> char {
-    let (left, right) = char_sets_1(line);
-    left.intersection(&right).next().copied().unwrap()
-}
-
-fn char_intersection_2(lines: &Vec<String>) -> char {
-    assert_eq!(lines.len(), 3);
-    let set1 = lines[0].chars().collect::<BTreeSet<_>>();
-    let set2 = lines[1].chars().collect::<BTreeSet<_>>();
-    let set3 = lines[2].chars().collect::<BTreeSet<_>>();
-    let set12 = set1.intersection(&set2).copied().collect::<BTreeSet<_>>();
-    set12.intersection(&set3).next().copied().unwrap()
+    let rucksacks = Rucksacks::from_input(&args.input()?)?;
+    if repair {
+        let plan = rucksacks.repair(GROUP_SIZE)?;
+        for transfer in plan.transfers.iter() {
+            eprintln!("{transfer}");
+        }
+        eprintln!("{} items moved", plan.moves);
+        for line in plan.lines.iter() {
+            println!("{line}");
+        }
+    } else {
+        println!("Part 1: {}", rucksacks.part1()?);
+        println!("Part 2: {}", rucksacks.part2()?);
+    }
+    Ok(())
 }
@@ -1,135 +1,53 @@
-use advent_code_lib::{all_lines, simpler_main};
-use std::collections::VecDeque;
+use advent2022::{
+    cli::DayArgs,
+    day5::{crane, CrateMover9000, CratePuzzle, Goal, Planner, CRANES},
+    Solution,
+};
 
-fn main() -> anyhow::Result<()> {
-    simpler_main(|filename| {
-        let mut puzzle1 = CratePuzzle::from_file(filename)?;
-        let mut puzzle2 = puzzle1.clone();
-        puzzle1.run1();
-        println!("Part 1: {}", puzzle1.state.tops());
-        puzzle2.run2();
-        println!("Part 2: {}", puzzle2.state.tops());
-        Ok(())
-    })
-}
-
-#[derive(Clone, Debug)]
-pub struct CratePuzzle {
-    state: CrateState,
-    script: Vec<CrateInstruction>,
-}
-
-#[derive(Clone, Debug)]
-pub struct CrateState {
-    stacks: Vec<VecDeque<char>>,
-}
-
-impl CrateState {
-    pub fn tops(&self) -> String {
-        self.stacks
-            .iter()
-            .map(|s| s.back().copied().unwrap())
-            .collect()
-    }
-}
-
-#[derive(Copy, Clone, Debug)]
-pub struct CrateInstruction {
-    pub quantity: usize,
-    pub start: usize,
-    pub end: usize,
+fn usage() -> String {
+    format!(
+        "Usage: day5 [--crane {CRANES}] [--inverse] [--goal file | --tops crates] [input file | -]"
+    )
 }
 
-impl CrateInstruction {
-    fn part1(&self, puzzle: &mut CrateState) {
-        let start = self.start - 1;
-        let end = self.end - 1;
-        for _ in 0..self.quantity {
-            let parcel = puzzle.stacks[start].pop_back().unwrap();
-            puzzle.stacks[end].push_back(parcel);
-        }
-    }
-
-    fn part2(&self, puzzle: &mut CrateState) {
-        let start = self.start - 1;
-        let end = self.end - 1;
-        let mut parcels = VecDeque::new();
-        for _ in 0..self.quantity {
-            let parcel = puzzle.stacks[start].pop_back().unwrap();
-            parcels.push_front(parcel);
-        }
-        while parcels.len() > 0 {
-            puzzle.stacks[end].push_back(parcels.pop_front().unwrap());
+fn main() -> anyhow::Result<()> {
+    let mut model = None;
+    let mut inverse = false;
+    let mut goal = None;
+    let mut args = DayArgs::new(5, usage().as_str());
+    while let Some(option) = args.option()? {
+        match option.as_str() {
+            "--crane" => model = Some(crane(args.value::<String>()?.as_str())?),
+            "--inverse" => inverse = true,
+            "--goal" => {
+                let target = CratePuzzle::from_file(args.value::<String>()?.as_str())?;
+                goal = Some(Goal::Arrangement(target.state));
+            }
+            "--tops" => goal = Some(Goal::Tops(args.value()?)),
+            _ => return Err(args.usage()),
         }
     }
-}
-
-fn decode_char(c: Option<&char>) -> Option<char> {
-    match c {
-        None => None,
-        Some(c) => match *c {
-            'A'..='Z' => Some(*c),
-            _ => None,
-        },
-    }
-}
-
-impl CratePuzzle {
-    pub fn from_file(filename: &str) -> anyhow::Result<Self> {
-        let mut stacks = vec![];
-        let mut state = None;
-        let mut script = vec![];
-        for line in all_lines(filename)? {
-            if line.starts_with("move") {
-                let line = line
-                    .replace("move", "to")
-                    .replace("from", "")
-                    .replace("to", "");
-                let nums = line
-                    .split_whitespace()
-                    .map(|n| n.parse::<usize>().unwrap())
-                    .collect::<Vec<_>>();
-                script.push(CrateInstruction {
-                    quantity: nums[0],
-                    start: nums[1],
-                    end: nums[2],
-                });
-            } else if line.starts_with(" 1   2   3") {
-                state = Some(CrateState {
-                    stacks: stacks.clone(),
-                });
-            } else if line.trim().len() > 0 {
-                let chars = line.chars().collect::<Vec<_>>();
-                let num_stacks = (chars.len() + 1) / 4;
-                if stacks.len() < num_stacks {
-                    for _ in 0..(num_stacks - stacks.len()) {
-                        stacks.push(VecDeque::new());
-                    }
-                }
-                (0..stacks.len())
-                    .map(|i| (i, decode_char(chars.get(1 + i * 4))))
-                    .for_each(|(i, c)| {
-                        if let Some(c) = c {
-                            stacks[i].push_front(c);
-                        }
-                    });
-            };
-        }
-        Ok(Self {
-            state: state.unwrap(),
-            script,
-        })
+    let puzzle = CratePuzzle::from_input(&args.input()?)?;
+    if let Some(goal) = goal {
+        let model = model.unwrap_or_else(|| Box::new(CrateMover9000));
+        let script = Planner::default().plan(&puzzle.state, &goal, model.as_ref())?;
+        print!(
+            "{}",
+            CratePuzzle::new(puzzle.name(), puzzle.state.clone(), script)
+        );
+        return Ok(());
     }
-
-    pub fn run1(&mut self) {
-        for line in self.script.iter() {
-            line.part1(&mut self.state);
-        }
+    if inverse {
+        let model = model.unwrap_or_else(|| Box::new(CrateMover9000));
+        print!("{}", puzzle.inverse(model.as_ref())?);
+        return Ok(());
     }
-
-    pub fn run2(&mut self) {
-        for line in self.script.iter() {
-            line.part2(&mut self.state);
+    match model {
+        Some(model) => println!("{}: {}", model.name(), puzzle.run(model.as_ref())?.tops()),
+        None => {
+            println!("Part 1: {}", puzzle.part1()?);
+            println!("Part 2: {}", puzzle.part2()?);
         }
     }
+    Ok(())
 }
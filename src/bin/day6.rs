@@ -1,29 +1,63 @@
-use std::collections::{BTreeSet, VecDeque};
+use std::{
+    fs,
+    io::{self, Read},
+};
 
-use advent_code_lib::{all_lines, simpler_main};
+use advent2022::{
+    cli::DayArgs,
+    day6::{first_markers, markers},
+    input::STDIN,
+};
 
-fn main() -> anyhow::Result<()> {
-    simpler_main(|filename| {
-        let line = all_lines(filename)?.next().unwrap();
-        println!("Part 1: {}", scanner(line.as_str(), 4));
-        println!("Part 2: {}", scanner(line.as_str(), 14));
-        Ok(())
-    })
-}
+const USAGE: &str = "Usage: day6 [--window length]... [--all] [input file | -]";
 
-fn scanner(line: &str, target_len: usize) -> usize {
-    let mut proc_count = 0;
-    let mut chars = line.chars();
-    let mut marker = VecDeque::new();
-    loop {
-        marker.push_back(chars.next().unwrap());
-        proc_count += 1;
-        if marker.len() > target_len {
-            marker.pop_front();
+/// The start-of-packet and start-of-message lengths.
+const WINDOWS: [usize; 2] = [4, 14];
+
+fn main() -> anyhow::Result<()> {
+    let mut lengths = vec![];
+    let mut all = false;
+    let mut args = DayArgs::new(6, USAGE);
+    while let Some(option) = args.option()? {
+        match option.as_str() {
+            "--window" => match args.value()? {
+                0 => return Err(args.usage()),
+                length => lengths.push(length),
+            },
+            "--all" => all = true,
+            _ => return Err(args.usage()),
+        }
+    }
+    let windows = lengths.is_empty();
+    if windows {
+        lengths.extend(WINDOWS);
+    }
+    let filename = args.filename();
+    let bytes = if filename == STDIN {
+        let mut bytes = vec![];
+        io::stdin().read_to_end(&mut bytes)?;
+        bytes
+    } else {
+        fs::read(filename.as_str())?
+    };
+    let line = bytes.strip_suffix(b"\n").unwrap_or(&bytes);
+    let line = line.strip_suffix(b"\r").unwrap_or(line);
+    let stream = line.iter().copied();
+    if all {
+        for marker in markers(stream, &lengths) {
+            println!("{} {}", marker.length, marker.position);
         }
-        let current = marker.iter().collect::<BTreeSet<_>>();
-        if current.len() == target_len {
-            return proc_count;
+    } else {
+        let positions = first_markers(stream, &lengths);
+        for (i, (length, position)) in lengths.iter().zip(positions).enumerate() {
+            let position = position.map_or("none".to_owned(), |p| p.to_string());
+            // The puzzle's own two windows answer its two parts.
+            if windows {
+                println!("Part {}: {position}", i + 1);
+            } else {
+                println!("{length}: {position}");
+            }
         }
     }
+    Ok(())
 }
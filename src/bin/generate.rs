@@ -0,0 +1,33 @@
+use std::{
+    env,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use advent2022::generate::generate;
+use anyhow::bail;
+
+const USAGE: &str = "Usage: generate <day> [size] [seed]";
+
+const DEFAULT_SIZE: usize = 20;
+
+fn main() -> anyhow::Result<()> {
+    let args = env::args().skip(1).collect::<Vec<_>>();
+    if args.is_empty() || args.len() > 3 {
+        bail!("{USAGE}");
+    }
+    let day = args[0].parse()?;
+    let size = match args.get(1) {
+        Some(size) => size.parse()?,
+        None => DEFAULT_SIZE,
+    };
+    let seed = match args.get(2) {
+        Some(seed) => seed.parse()?,
+        None => {
+            let seed = SystemTime::now().duration_since(UNIX_EPOCH)?.as_nanos() as u64;
+            eprintln!("seed {seed}");
+            seed
+        }
+    };
+    println!("{}", generate(day, size, seed)?);
+    Ok(())
+}
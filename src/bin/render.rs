@@ -0,0 +1,44 @@
+use std::env;
+
+use advent2022::{
+    input::Input,
+    render::{draw_day, Palette, Renderer},
+    runner::default_input,
+};
+use anyhow::bail;
+
+const USAGE: &str =
+    "Usage: render <day> <image file (.ppm, .png or .svg)> [--cell-size pixels] [--palette file] [input file | -]";
+
+fn main() -> anyhow::Result<()> {
+    let mut renderer = Renderer::default();
+    let mut positional = vec![];
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if arg.starts_with("--") {
+            let value = match args.next() {
+                Some(value) => value,
+                None => bail!("{USAGE}"),
+            };
+            match arg.as_str() {
+                "--cell-size" => renderer.cell_size = value.parse()?,
+                "--palette" => {
+                    renderer.palette = Palette::from_input(&Input::from_file(value.as_str())?)?
+                }
+                _ => bail!("{USAGE}"),
+            }
+        } else {
+            positional.push(arg);
+        }
+    }
+    if positional.len() < 2 || positional.len() > 3 {
+        bail!("{USAGE}");
+    }
+    let day = positional[0].parse()?;
+    let filename = positional
+        .get(2)
+        .cloned()
+        .unwrap_or_else(|| default_input(day));
+    let grid = draw_day(day, &Input::from_file(filename.as_str())?)?;
+    renderer.save(&grid, positional[1].as_str())
+}
@@ -0,0 +1,44 @@
+use std::{
+    env,
+    io::{self, IsTerminal},
+};
+
+use advent2022::{
+    input::Input,
+    runner::default_input,
+    simulation::simulate_day,
+    stepper::{interact, Stepper},
+};
+use anyhow::bail;
+
+const USAGE: &str = "Usage: step <day> [--history n] [input file]";
+
+fn main() -> anyhow::Result<()> {
+    let mut history = 1000;
+    let mut positional = vec![];
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--history" => match args.next() {
+                Some(value) => history = value.parse()?,
+                None => bail!("{USAGE}"),
+            },
+            _ => positional.push(arg),
+        }
+    }
+    if positional.is_empty() || positional.len() > 2 {
+        bail!("{USAGE}");
+    }
+    let day = positional[0].parse()?;
+    let filename = positional
+        .get(1)
+        .cloned()
+        .unwrap_or_else(|| default_input(day));
+    if filename == "-" {
+        bail!("Commands are read from stdin, so the input must come from a file");
+    }
+    let simulation = simulate_day(day, &Input::from_file(filename.as_str())?)?;
+    let mut stepper = Stepper::new(simulation, history);
+    let clear = io::stdout().is_terminal();
+    interact(&mut stepper, io::stdin().lock(), &mut io::stdout(), clear)
+}
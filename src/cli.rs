@@ -0,0 +1,102 @@
+use std::{env, str::FromStr};
+
+use anyhow::anyhow;
+
+use crate::{input::Input, run, runner::default_input, Solution};
+
+/// The command line of a day's binary: `--` options in any order, and at
+/// most one input file, which may be `-` for standard input and defaults to
+/// the day's example.
+pub struct DayArgs {
+    day: usize,
+    usage: String,
+    args: std::vec::IntoIter<String>,
+    filename: Option<String>,
+}
+
+impl DayArgs {
+    pub fn new(day: usize, usage: &str) -> Self {
+        Self::from_args(day, usage, env::args().skip(1).collect())
+    }
+
+    pub fn from_args(day: usize, usage: &str, args: Vec<String>) -> Self {
+        Self {
+            day,
+            usage: usage.to_owned(),
+            args: args.into_iter(),
+            filename: None,
+        }
+    }
+
+    /// The next option, setting aside the input file if it comes first.
+    pub fn option(&mut self) -> anyhow::Result<Option<String>> {
+        for arg in self.args.by_ref() {
+            if arg.starts_with("--") {
+                return Ok(Some(arg));
+            } else if self.filename.is_none() {
+                self.filename = Some(arg);
+            } else {
+                return Err(self.usage());
+            }
+        }
+        Ok(None)
+    }
+
+    /// The value following the current option.
+    pub fn value<T: FromStr>(&mut self) -> anyhow::Result<T> {
+        match self.args.next().map(|value| value.parse()) {
+            Some(Ok(value)) => Ok(value),
+            _ => Err(self.usage()),
+        }
+    }
+
+    pub fn usage(&self) -> anyhow::Error {
+        anyhow!("{}", self.usage)
+    }
+
+    pub fn filename(&self) -> String {
+        self.filename
+            .clone()
+            .unwrap_or_else(|| default_input(self.day))
+    }
+
+    pub fn input(&self) -> anyhow::Result<Input> {
+        Input::from_file(self.filename().as_str())
+    }
+}
+
+/// The whole of a day's binary that has no options of its own.
+pub fn run_day<S: Solution>(day: usize) -> anyhow::Result<()> {
+    let mut args = DayArgs::new(day, format!("Usage: day{day} [input file | -]").as_str());
+    if args.option()?.is_some() {
+        return Err(args.usage());
+    }
+    run::<S>(args.filename().as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{cli::DayArgs, input::STDIN, runner::default_input};
+
+    fn day_args(args: &[&str]) -> DayArgs {
+        DayArgs::from_args(6, "usage", args.iter().map(|s| s.to_string()).collect())
+    }
+
+    #[test]
+    fn test_day_args() {
+        let mut args = day_args(&[STDIN, "--window", "7", "--all"]);
+        assert_eq!(args.option().unwrap().as_deref(), Some("--window"));
+        assert_eq!(args.value::<usize>().unwrap(), 7);
+        assert_eq!(args.option().unwrap().as_deref(), Some("--all"));
+        assert_eq!(args.option().unwrap(), None);
+        assert_eq!(args.filename(), STDIN);
+
+        let mut args = day_args(&["--window"]);
+        args.option().unwrap();
+        assert_eq!(args.value::<usize>().unwrap_err().to_string(), "usage");
+        assert_eq!(args.filename(), default_input(6));
+
+        let mut args = day_args(&["a.txt", "b.txt"]);
+        assert_eq!(args.option().unwrap_err().to_string(), "usage");
+    }
+}
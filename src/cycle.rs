@@ -0,0 +1,261 @@
+use std::{collections::HashMap, hash::Hash};
+
+use anyhow::bail;
+
+/// A state that advances one step at a time, and eventually repeats.
+pub trait Cyclic: Clone {
+    /// Equal fingerprints must mean equal futures.
+    type Fingerprint: Eq + Hash;
+
+    fn advance(&mut self);
+
+    fn fingerprint(&self) -> Self::Fingerprint;
+
+    /// The quantity to extrapolate, which must grow by the same amount each cycle.
+    fn metric(&self) -> i64 {
+        0
+    }
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum Method {
+    /// Remembers the step each fingerprint was first seen at.
+    #[default]
+    Table,
+    /// Brent's algorithm, keeping two states and no table.
+    Brent,
+    /// Floyd's tortoise and hare, keeping two states and no table.
+    Floyd,
+}
+
+/// Finds where a `Cyclic` state starts repeating.
+#[derive(Copy, Clone, Debug)]
+pub struct CycleDetector {
+    pub method: Method,
+    /// Extra cycles to step through, checking that they repeat the first one.
+    pub verify: usize,
+    /// Gives up after this many steps.
+    pub max_steps: usize,
+}
+
+impl Default for CycleDetector {
+    fn default() -> Self {
+        Self {
+            method: Method::default(),
+            verify: 1,
+            max_steps: 10_000_000,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Cycle {
+    /// Steps taken before the first cycle begins.
+    pub start: usize,
+    /// Steps in each cycle.
+    pub length: usize,
+    /// The metric after each step up to the end of the first cycle.
+    metrics: Vec<i64>,
+}
+
+impl Cycle {
+    /// How much the metric grows over each cycle.
+    pub fn gain(&self) -> i64 {
+        self.metrics[self.start + self.length] - self.metrics[self.start]
+    }
+
+    /// The metric after `step` steps, however many that is.
+    pub fn metric_at(&self, step: usize) -> i64 {
+        if step < self.metrics.len() {
+            self.metrics[step]
+        } else {
+            let cycles = (step - self.start) / self.length;
+            let offset = (step - self.start) % self.length;
+            self.metrics[self.start + offset] + cycles as i64 * self.gain()
+        }
+    }
+}
+
+impl CycleDetector {
+    pub fn find<C: Cyclic>(&self, initial: &C) -> anyhow::Result<Cycle> {
+        let (start, length) = match self.method {
+            Method::Table => self.table(initial)?,
+            Method::Brent => self.brent(initial)?,
+            Method::Floyd => self.floyd(initial)?,
+        };
+        self.measure(initial, start, length)
+    }
+
+    fn table<C: Cyclic>(&self, initial: &C) -> anyhow::Result<(usize, usize)> {
+        let mut state = initial.clone();
+        let mut first_seen = HashMap::new();
+        for step in 0..=self.max_steps {
+            if let Some(start) = first_seen.insert(state.fingerprint(), step) {
+                return Ok((start, step - start));
+            }
+            state.advance();
+        }
+        self.gave_up()
+    }
+
+    fn brent<C: Cyclic>(&self, initial: &C) -> anyhow::Result<(usize, usize)> {
+        let mut steps = 0;
+        let mut power = 1;
+        let mut length = 1;
+        let mut tortoise = initial.clone();
+        let mut hare = initial.clone();
+        hare.advance();
+        while tortoise.fingerprint() != hare.fingerprint() {
+            if power == length {
+                tortoise = hare.clone();
+                power *= 2;
+                length = 0;
+            }
+            hare.advance();
+            length += 1;
+            steps += 1;
+            if steps > self.max_steps {
+                return self.gave_up();
+            }
+        }
+
+        let mut tortoise = initial.clone();
+        let mut hare = initial.clone();
+        for _ in 0..length {
+            hare.advance();
+        }
+        Ok((Self::meet(&mut tortoise, &mut hare), length))
+    }
+
+    fn floyd<C: Cyclic>(&self, initial: &C) -> anyhow::Result<(usize, usize)> {
+        let mut steps = 0;
+        let mut tortoise = initial.clone();
+        let mut hare = initial.clone();
+        loop {
+            tortoise.advance();
+            hare.advance();
+            hare.advance();
+            if tortoise.fingerprint() == hare.fingerprint() {
+                break;
+            }
+            steps += 1;
+            if steps > self.max_steps {
+                return self.gave_up();
+            }
+        }
+
+        let mut tortoise = initial.clone();
+        let start = Self::meet(&mut tortoise, &mut hare);
+        let mut hare = tortoise.clone();
+        let mut length = 1;
+        hare.advance();
+        while tortoise.fingerprint() != hare.fingerprint() {
+            hare.advance();
+            length += 1;
+        }
+        Ok((start, length))
+    }
+
+    /// Advances both states together until they match, returning the steps taken.
+    fn meet<C: Cyclic>(tortoise: &mut C, hare: &mut C) -> usize {
+        let mut steps = 0;
+        while tortoise.fingerprint() != hare.fingerprint() {
+            tortoise.advance();
+            hare.advance();
+            steps += 1;
+        }
+        steps
+    }
+
+    /// Replays the steps up to the end of the first cycle, recording the
+    /// metric, then checks the next `verify` cycles against it.
+    fn measure<C: Cyclic>(
+        &self,
+        initial: &C,
+        start: usize,
+        length: usize,
+    ) -> anyhow::Result<Cycle> {
+        let mut state = initial.clone();
+        let mut metrics = vec![state.metric()];
+        let mut fingerprints = vec![state.fingerprint()];
+        for _ in 0..start + length {
+            state.advance();
+            metrics.push(state.metric());
+            fingerprints.push(state.fingerprint());
+        }
+        let cycle = Cycle {
+            start,
+            length,
+            metrics,
+        };
+        for step in start + length + 1..=start + length * (1 + self.verify) {
+            state.advance();
+            let earlier = step - length;
+            if state.fingerprint() != fingerprints[earlier]
+                || state.metric() != cycle.metric_at(earlier) + cycle.gain()
+            {
+                bail!(
+                    "The cycle of {length} steps from step {start} does not repeat at step {step}"
+                );
+            }
+            fingerprints.push(state.fingerprint());
+        }
+        Ok(cycle)
+    }
+
+    fn gave_up<T>(&self) -> anyhow::Result<T> {
+        bail!("No cycle found within {} steps", self.max_steps)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cycle::{CycleDetector, Cyclic, Method};
+
+    /// Counts up by 3 from 0, modulo 100 once past 50.
+    #[derive(Clone)]
+    struct Counter(i64);
+
+    impl Cyclic for Counter {
+        type Fingerprint = i64;
+
+        fn advance(&mut self) {
+            self.0 += 3;
+        }
+
+        fn fingerprint(&self) -> i64 {
+            if self.0 < 50 {
+                self.0
+            } else {
+                50 + self.0 % 100
+            }
+        }
+
+        fn metric(&self) -> i64 {
+            self.0
+        }
+    }
+
+    #[test]
+    fn test_methods_agree() {
+        for method in [Method::Table, Method::Brent, Method::Floyd] {
+            let detector = CycleDetector {
+                method,
+                ..CycleDetector::default()
+            };
+            let cycle = detector.find(&Counter(0)).unwrap();
+            assert_eq!((cycle.start, cycle.length), (17, 100), "{method:?}");
+            assert_eq!(cycle.gain(), 300);
+            assert_eq!(cycle.metric_at(1_000_000_000), 3_000_000_000);
+        }
+    }
+
+    #[test]
+    fn test_give_up() {
+        let detector = CycleDetector {
+            max_steps: 50,
+            ..CycleDetector::default()
+        };
+        assert!(detector.find(&Counter(0)).is_err());
+    }
+}
@@ -0,0 +1,431 @@
+use std::{cmp::Reverse, collections::BinaryHeap, fmt::Display};
+
+use anyhow::{anyhow, bail};
+
+use crate::{
+    input::Input,
+    parse::{column_of, parse_token, ParseError},
+    Solution,
+};
+
+/// How many elves part 2 shares the snacks between.
+const TOP_ELVES: usize = 3;
+
+#[derive(Clone, Debug)]
+pub struct Elves {
+    tally: CalorieTally,
+}
+
+impl Elves {
+    pub fn from_file(filename: &str) -> anyhow::Result<Self> {
+        Self::from_input(&Input::from_file(filename)?)
+    }
+
+    /// Keeps only the top elves, so memory does not grow with the input.
+    pub fn from_input(input: &Input) -> anyhow::Result<Self> {
+        Ok(Self {
+            tally: CalorieTally::from_input(input, TOP_ELVES)?,
+        })
+    }
+
+    pub fn tally(&self) -> &CalorieTally {
+        &self.tally
+    }
+}
+
+impl Solution for Elves {
+    type Part1 = i64;
+    type Part2 = i64;
+
+    fn load(input: &Input) -> anyhow::Result<Self> {
+        Self::from_input(input)
+    }
+
+    fn part1(&self) -> anyhow::Result<i64> {
+        match self.tally.top().first() {
+            Some((_, total)) => Ok(*total),
+            None => Err(anyhow!("There are no elves")),
+        }
+    }
+
+    fn part2(&self) -> anyhow::Result<i64> {
+        Ok(self.tally.top_sum())
+    }
+
+    fn diagnostics(&self) -> Vec<(String, String)> {
+        let tally = &self.tally;
+        let top = tally
+            .top()
+            .iter()
+            .map(|(elf, _)| elf.to_string())
+            .collect::<Vec<_>>();
+        vec![
+            ("elves".to_owned(), format!("{}", tally.count())),
+            ("mean".to_owned(), format!("{:.1}", tally.mean())),
+            ("top elves".to_owned(), top.join(" ")),
+        ]
+    }
+}
+
+/// Calls `visit` with each food item's calories, and with `None` at the end
+/// of each elf's items. Extra blank lines do not make empty elves.
+fn for_each_item<F: FnMut(Option<i64>)>(input: &Input, mut visit: F) -> Result<(), ParseError> {
+    let mut carrying = false;
+    for (i, line) in input.lines().enumerate() {
+        let food = line.trim();
+        if food.is_empty() {
+            if carrying {
+                visit(None);
+                carrying = false;
+            }
+        } else {
+            let column = column_of(line, line.find(food).unwrap());
+            let calories = parse_token(column, food, "a calorie count")
+                .map_err(|e| e.at(input.name(), i + 1))?;
+            visit(Some(calories));
+            carrying = true;
+        }
+    }
+    if carrying {
+        visit(None);
+    }
+    Ok(())
+}
+
+/// Calorie totals for a stream of elves, keeping the `k` largest in a heap.
+/// Elves are numbered from 0 in the order they arrive. The median and
+/// percentiles need every total, so only a tally made `with_totals` keeps
+/// them; otherwise memory is bounded by `k`.
+#[derive(Clone, Debug)]
+pub struct CalorieTally {
+    k: usize,
+    top: BinaryHeap<Reverse<(i64, Reverse<usize>)>>,
+    count: usize,
+    sum: i64,
+    totals: Option<Vec<i64>>,
+}
+
+impl CalorieTally {
+    pub fn new(k: usize) -> Self {
+        Self {
+            k,
+            top: BinaryHeap::with_capacity(k + 1),
+            count: 0,
+            sum: 0,
+            totals: None,
+        }
+    }
+
+    /// A tally that also keeps every total, for `median` and `percentile`.
+    pub fn with_totals(k: usize) -> Self {
+        Self {
+            totals: Some(vec![]),
+            ..Self::new(k)
+        }
+    }
+
+    /// Tallies each blank-line-separated group of calorie counts as one elf.
+    pub fn from_input(input: &Input, k: usize) -> Result<Self, ParseError> {
+        let mut tally = Self::new(k);
+        tally.add_input(input)?;
+        Ok(tally)
+    }
+
+    pub fn add_input(&mut self, input: &Input) -> Result<(), ParseError> {
+        let mut elf = 0;
+        for_each_item(input, |item| match item {
+            Some(calories) => elf += calories,
+            None => {
+                self.add(elf);
+                elf = 0;
+            }
+        })
+    }
+
+    pub fn add(&mut self, total: i64) {
+        let elf = self.count;
+        self.count += 1;
+        self.sum += total;
+        if let Some(totals) = &mut self.totals {
+            totals.push(total);
+        }
+        self.top.push(Reverse((total, Reverse(elf))));
+        if self.top.len() > self.k {
+            self.top.pop();
+        }
+    }
+
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// The `k` largest totals as `(elf, total)`, largest first; ties go to
+    /// the earlier elf.
+    pub fn top(&self) -> Vec<(usize, i64)> {
+        let mut top = self
+            .top
+            .iter()
+            .map(|Reverse((total, Reverse(elf)))| (*elf, *total))
+            .collect::<Vec<_>>();
+        top.sort_by_key(|(elf, total)| (Reverse(*total), *elf));
+        top
+    }
+
+    pub fn top_sum(&self) -> i64 {
+        self.top.iter().map(|Reverse((total, _))| total).sum()
+    }
+
+    pub fn mean(&self) -> f64 {
+        self.sum as f64 / self.count() as f64
+    }
+
+    /// None unless the tally keeps its totals and has at least one elf.
+    fn totals(&self) -> Option<Vec<i64>> {
+        self.totals.clone().filter(|totals| !totals.is_empty())
+    }
+
+    pub fn median(&self) -> Option<f64> {
+        let mut totals = self.totals()?;
+        let middle = totals.len() / 2;
+        let upper = *totals.select_nth_unstable(middle).1;
+        Some(if !totals.len().is_multiple_of(2) {
+            upper as f64
+        } else {
+            let lower = *totals[..middle].iter().max().unwrap();
+            (lower + upper) as f64 / 2.0
+        })
+    }
+
+    /// The smallest total that at least `p` percent of elves do not exceed.
+    pub fn percentile(&self, p: f64) -> Option<i64> {
+        let mut totals = self.totals()?;
+        let rank = (p / 100.0 * totals.len() as f64).ceil() as usize;
+        let rank = rank.clamp(1, totals.len());
+        Some(*totals.select_nth_unstable(rank - 1).1)
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Elf {
+    items: Vec<i64>,
+}
+
+impl Elf {
+    pub fn new(items: Vec<i64>) -> Self {
+        Self { items }
+    }
+
+    pub fn items(&self) -> &[i64] {
+        &self.items
+    }
+
+    pub fn total(&self) -> i64 {
+        self.items.iter().sum()
+    }
+}
+
+/// Moving one food item from one elf to another. Elves and their items are
+/// numbered from 0, as they were before any items moved.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Transfer {
+    pub from: usize,
+    pub item: usize,
+    pub calories: i64,
+    pub to: usize,
+}
+
+impl Display for Transfer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "move item {} ({} calories) from elf {} to elf {}",
+            self.item, self.calories, self.from, self.to
+        )
+    }
+}
+
+/// Every elf's food items, in the order they were listed.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Inventory {
+    elves: Vec<Elf>,
+}
+
+impl Inventory {
+    pub fn from_file(filename: &str) -> anyhow::Result<Self> {
+        Self::from_input(&Input::from_file(filename)?)
+    }
+
+    pub fn from_input(input: &Input) -> anyhow::Result<Self> {
+        let mut elves = vec![];
+        let mut items = vec![];
+        for_each_item(input, |item| match item {
+            Some(calories) => items.push(calories),
+            None => elves.push(Elf::new(std::mem::take(&mut items))),
+        })?;
+        Ok(Self { elves })
+    }
+
+    pub fn elves(&self) -> &[Elf] {
+        &self.elves
+    }
+
+    pub fn tally(&self, k: usize) -> CalorieTally {
+        let mut tally = CalorieTally::with_totals(k);
+        for elf in self.elves.iter() {
+            tally.add(elf.total());
+        }
+        tally
+    }
+
+    /// The elf carrying the single most calorific item, and that item's
+    /// calories; ties go to the earlier elf.
+    pub fn heaviest_item(&self) -> Option<(usize, i64)> {
+        self.elves
+            .iter()
+            .enumerate()
+            .filter_map(|(i, elf)| elf.items.iter().max().map(|c| (i, *c)))
+            .min_by_key(|(i, calories)| (Reverse(*calories), *i))
+    }
+
+    /// The elves carrying more than `threshold` calories in total.
+    pub fn above(&self, threshold: i64) -> Vec<usize> {
+        (0..self.elves.len())
+            .filter(|i| self.elves[*i].total() > threshold)
+            .collect()
+    }
+
+    /// Transfers that leave no elf carrying more than `cap` calories. Each
+    /// overloaded elf gives up its largest items until it is within the cap,
+    /// and each item goes to whichever elf then has the most room for it.
+    /// This is greedy, so it can fail where some other packing would fit.
+    pub fn rebalance(&self, cap: i64) -> anyhow::Result<Vec<Transfer>> {
+        let mut totals = self.elves.iter().map(|e| e.total()).collect::<Vec<_>>();
+        let mut surplus = vec![];
+        for (from, elf) in self.elves.iter().enumerate() {
+            let mut items = elf.items.iter().copied().enumerate().collect::<Vec<_>>();
+            items.sort_by_key(|(item, calories)| (Reverse(*calories), *item));
+            for (item, calories) in items {
+                if totals[from] <= cap {
+                    break;
+                }
+                totals[from] -= calories;
+                surplus.push((from, item, calories));
+            }
+        }
+        surplus.sort_by_key(|(from, item, calories)| (Reverse(*calories), *from, *item));
+
+        let mut transfers = vec![];
+        for (from, item, calories) in surplus {
+            let to = (0..totals.len())
+                .filter(|to| *to != from)
+                .min_by_key(|to| (totals[*to], *to))
+                .filter(|to| totals[*to] + calories <= cap);
+            match to {
+                Some(to) => {
+                    totals[to] += calories;
+                    transfers.push(Transfer {
+                        from,
+                        item,
+                        calories,
+                        to,
+                    });
+                }
+                None => bail!(
+                    "No greedy packing found: no elf has room for elf {from}'s \
+                     {calories}-calorie item under a cap of {cap}"
+                ),
+            }
+        }
+        Ok(transfers)
+    }
+
+    /// One row per food item, as `elf,item,calories`.
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("elf,item,calories\n");
+        for (i, elf) in self.elves.iter().enumerate() {
+            for (j, calories) in elf.items.iter().enumerate() {
+                csv.push_str(format!("{i},{j},{calories}\n").as_str());
+            }
+        }
+        csv
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        day1::{CalorieTally, Elves, Inventory},
+        input::Input,
+        Solution,
+    };
+
+    #[test]
+    fn test_tally() {
+        let input = Input::from_file("ex/day1.txt").unwrap();
+        let mut tally = CalorieTally::with_totals(2);
+        tally.add_input(&input).unwrap();
+        assert_eq!(tally.count(), 5);
+        assert_eq!(tally.top(), vec![(3, 24000), (2, 11000)]);
+        assert_eq!(tally.top_sum(), 35000);
+        assert_eq!(tally.mean(), 11000.0);
+        assert_eq!(tally.median(), Some(10000.0));
+        assert_eq!(tally.percentile(0.0), Some(4000));
+        assert_eq!(tally.percentile(80.0), Some(11000));
+        assert_eq!(tally.percentile(100.0), Some(24000));
+
+        let bounded = CalorieTally::from_input(&input, 2).unwrap();
+        assert_eq!(bounded.top(), tally.top());
+        assert_eq!(bounded.median(), None);
+
+        let mut tally = CalorieTally::with_totals(2);
+        for total in [5, 9, 5, 9, 1, 9] {
+            tally.add(total);
+        }
+        assert_eq!(tally.top(), vec![(1, 9), (3, 9)]);
+        assert_eq!(tally.median(), Some(7.0));
+    }
+
+    #[test]
+    fn test_no_elves() {
+        let elves = Elves::from_input(&Input::from("\n\n")).unwrap();
+        assert!(elves.part1().is_err());
+        assert!(elves.tally().median().is_none());
+    }
+
+    #[test]
+    fn test_blank_lines() {
+        let input = Input::from("1\n\n\n2\n\n");
+        let tally = CalorieTally::from_input(&input, 3).unwrap();
+        assert_eq!(tally.count(), 2);
+        assert_eq!(tally.top(), vec![(1, 2), (0, 1)]);
+        let inventory = Inventory::from_input(&input).unwrap();
+        assert_eq!(inventory.elves().len(), 2);
+    }
+
+    #[test]
+    fn test_inventory() {
+        let inventory = Inventory::from_file("ex/day1.txt").unwrap();
+        assert_eq!(inventory.elves().len(), 5);
+        assert_eq!(inventory.elves()[3].items(), &[7000, 8000, 9000]);
+        assert_eq!(inventory.heaviest_item(), Some((4, 10000)));
+        assert_eq!(inventory.above(10000), vec![2, 3]);
+        assert!(inventory
+            .to_csv()
+            .starts_with("elf,item,calories\n0,0,1000\n"));
+
+        let transfers = inventory.rebalance(15000).unwrap();
+        let mut totals = inventory
+            .elves()
+            .iter()
+            .map(|e| e.total())
+            .collect::<Vec<_>>();
+        for t in transfers.iter() {
+            assert_eq!(inventory.elves()[t.from].items()[t.item], t.calories);
+            totals[t.from] -= t.calories;
+            totals[t.to] += t.calories;
+        }
+        assert!(totals.iter().all(|t| *t <= 15000), "{totals:?}");
+        assert_eq!(transfers.len(), 1);
+        assert!(inventory.rebalance(9000).is_err());
+    }
+}
@@ -0,0 +1,116 @@
+use crate::{
+    input::Input,
+    parse::{parse_lines, Fields, LineError, LineResult},
+    Solution,
+};
+
+pub struct Cpu {
+    x: i64,
+    completed_cycles: usize,
+    signal_strengths: Vec<i64>,
+    x_values: Vec<i64>,
+}
+
+impl Cpu {
+    pub fn new() -> Self {
+        Cpu {
+            x: 1,
+            completed_cycles: 0,
+            signal_strengths: vec![],
+            x_values: vec![],
+        }
+    }
+
+    pub fn instruction(&mut self, instruction: &str) -> LineResult<()> {
+        let mut fields = Fields::new(instruction);
+        let (column, opcode) = fields.next("noop or addx")?;
+        match opcode {
+            "noop" => {
+                fields.end()?;
+                self.record_state();
+            }
+            "addx" => {
+                let v = fields.parse::<i64>("an integer")?;
+                fields.end()?;
+                self.record_state();
+                self.record_state();
+                self.x += v;
+            }
+            _ => return Err(LineError::new(column, "noop or addx", opcode)),
+        }
+        Ok(())
+    }
+
+    pub fn record_state(&mut self) {
+        self.completed_cycles += 1;
+        self.signal_strengths
+            .push(self.completed_cycles as i64 * self.x);
+        self.x_values.push(self.x);
+    }
+
+    pub fn strength_during(&self, cycle: usize) -> i64 {
+        self.signal_strengths[cycle - 1]
+    }
+
+    pub fn from_file(filename: &str) -> anyhow::Result<Self> {
+        Self::from_input(&Input::from_file(filename)?)
+    }
+
+    pub fn from_input(input: &Input) -> anyhow::Result<Self> {
+        let mut cpu = Cpu::new();
+        parse_lines(input, |line| cpu.instruction(line))?;
+        Ok(cpu)
+    }
+
+    pub fn render(&self) -> String {
+        let on: Vec<bool> = self
+            .x_values
+            .iter()
+            .copied()
+            .enumerate()
+            .map(|(i, x)| {
+                let crt_x = (i % 40) as i64;
+                (x - 1..=x + 1).contains(&crt_x)
+            })
+            .collect();
+        let mut picture = String::new();
+        for (i, b) in on.iter().enumerate() {
+            if i % 40 == 0 {
+                picture.push('\n');
+            }
+            picture.push(if *b { '#' } else { '.' });
+        }
+        picture
+    }
+}
+
+impl Solution for Cpu {
+    type Part1 = i64;
+    type Part2 = String;
+
+    fn load(input: &Input) -> anyhow::Result<Self> {
+        Self::from_input(input)
+    }
+
+    fn part1(&self) -> anyhow::Result<i64> {
+        Ok((20..=220)
+            .step_by(40)
+            .map(|cycle| self.strength_during(cycle))
+            .sum())
+    }
+
+    fn part2(&self) -> anyhow::Result<String> {
+        Ok(self.render())
+    }
+
+    fn diagnostics(&self) -> Vec<(String, String)> {
+        vec![
+            ("cycles".to_owned(), format!("{}", self.completed_cycles)),
+            ("final x".to_owned(), format!("{}", self.x)),
+        ]
+    }
+
+    fn drawing(&self) -> Option<String> {
+        Some(self.render())
+    }
+}
@@ -0,0 +1,260 @@
+use std::fmt::Debug;
+use std::{collections::VecDeque, str::FromStr};
+
+use anyhow::bail;
+use bare_metal_modulo::*;
+
+use crate::{
+    input::Input,
+    parse::{Fields, LineError, LineResult, NumberedLines},
+    Solution,
+};
+
+pub fn evaluate(mut monkeys: MonkeyTroop, total_rounds: usize) -> u128 {
+    for _ in 0..total_rounds {
+        monkeys.round();
+    }
+    monkeys.monkey_business()
+}
+
+#[derive(Debug, Copy, Clone)]
+pub enum OpCode {
+    Plus,
+    Times,
+}
+
+impl OpCode {
+    fn eval(&self, left: ModNum<i64>, right: ModNum<i64>) -> ModNum<i64> {
+        match self {
+            OpCode::Plus => left + right,
+            OpCode::Times => left * right,
+        }
+    }
+}
+
+impl FromStr for OpCode {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "+" => Ok(Self::Plus),
+            "*" => Ok(Self::Times),
+            _ => bail!("{s}: Not supported"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Operation {
+    left: Option<i64>,
+    right: Option<i64>,
+    op: OpCode,
+}
+
+impl Operation {
+    pub fn from(s: &str) -> LineResult<Self> {
+        let mut fields = Fields::new(s);
+        fields.expect("Operation:")?;
+        fields.expect("new")?;
+        fields.expect("=")?;
+        let left = Self::operand(&mut fields)?;
+        let op = fields.parse::<OpCode>("+ or *")?;
+        let right = Self::operand(&mut fields)?;
+        fields.end()?;
+        Ok(Operation { left, right, op })
+    }
+
+    fn operand(fields: &mut Fields) -> LineResult<Option<i64>> {
+        let (column, operand) = fields.next("old or an integer")?;
+        if operand == "old" {
+            Ok(None)
+        } else {
+            operand
+                .parse()
+                .map(Some)
+                .map_err(|_| LineError::new(column, "old or an integer", operand))
+        }
+    }
+
+    pub fn eval_on(&self, old: ModNum<i64>) -> ModNum<i64> {
+        self.op.eval(
+            Self::convert(self.left, old),
+            Self::convert(self.right, old),
+        )
+    }
+
+    pub fn convert(value: Option<i64>, old: ModNum<i64>) -> ModNum<i64> {
+        value.map_or(old, |v| ModNum::new(v, old.m()))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Monkey {
+    items: VecDeque<ModNum<i64>>,
+    op: Operation,
+    div_test_value: i64,
+    true_monkey: usize,
+    false_monkey: usize,
+    total_inspections: u128,
+}
+
+fn last_num_after<N: FromStr>(s: &str, words: &[&str], expected: &str) -> LineResult<N> {
+    let mut fields = Fields::new(s);
+    for word in words.iter() {
+        fields.expect(word)?;
+    }
+    let n = fields.parse(expected)?;
+    fields.end()?;
+    Ok(n)
+}
+
+impl Monkey {
+    pub fn from_lines(lines: &mut NumberedLines) -> anyhow::Result<Self> {
+        lines.parse_next("Monkey header", |line| {
+            let mut fields = Fields::new(line);
+            fields.expect("Monkey")?;
+            let (column, id) = fields.next("a monkey number and colon")?;
+            match id.strip_suffix(':').map(|id| id.parse::<usize>()) {
+                Some(Ok(_)) => fields.end(),
+                _ => Err(LineError::new(column, "a monkey number and colon", id)),
+            }
+        })?;
+        let items = lines.parse_next("starting items", |line| {
+            let mut fields = Fields::split(line, &[' ', ',']);
+            fields.expect("Starting")?;
+            fields.expect("items:")?;
+            fields
+                .rest()
+                .iter()
+                .map(|(column, item)| {
+                    item.parse::<i64>()
+                        .map_err(|_| LineError::new(*column, "a worry level", item))
+                })
+                .collect::<LineResult<VecDeque<i64>>>()
+        })?;
+        let product = items.iter().map(|n| n + 1).product();
+        let items = items.iter().map(|n| ModNum::new(*n, product)).collect();
+        let op = lines.parse_next("an operation", Operation::from)?;
+        let div_test_value = lines.parse_next("a test", |line| {
+            last_num_after(line, &["Test:", "divisible", "by"], "a divisor")
+        })?;
+        let true_monkey = lines.parse_next("a true target", |line| {
+            last_num_after(
+                line,
+                &["If", "true:", "throw", "to", "monkey"],
+                "a monkey number",
+            )
+        })?;
+        let false_monkey = lines.parse_next("a false target", |line| {
+            last_num_after(
+                line,
+                &["If", "false:", "throw", "to", "monkey"],
+                "a monkey number",
+            )
+        })?;
+        lines.skip_blank()?;
+        let total_inspections = 0;
+        Ok(Self {
+            items,
+            op,
+            div_test_value,
+            true_monkey,
+            false_monkey,
+            total_inspections,
+        })
+    }
+}
+
+#[derive(Clone)]
+pub struct MonkeyTroop {
+    monkeys: Vec<Monkey>,
+    worry_div: Option<i64>,
+}
+
+impl MonkeyTroop {
+    pub fn from_file(filename: &str, worry_div: Option<i64>) -> anyhow::Result<MonkeyTroop> {
+        Self::from_input(&Input::from_file(filename)?, worry_div)
+    }
+
+    pub fn from_input(input: &Input, worry_div: Option<i64>) -> anyhow::Result<MonkeyTroop> {
+        let mut monkeys = vec![];
+        let mut lines = NumberedLines::new(input);
+        while !lines.at_end() {
+            monkeys.push(Monkey::from_lines(&mut lines)?);
+        }
+        for monkey in monkeys.iter() {
+            for target in [monkey.true_monkey, monkey.false_monkey] {
+                if target >= monkeys.len() {
+                    bail!("{}: Monkey {target} does not exist", input.name());
+                }
+            }
+        }
+        let gcf = monkeys.iter().map(|m| m.div_test_value).product();
+        for monkey in monkeys.iter_mut() {
+            for item in monkey.items.iter_mut() {
+                *item = ModNum::new(item.a(), gcf);
+            }
+        }
+        Ok(Self { monkeys, worry_div })
+    }
+
+    pub fn monkey_business(&self) -> u128 {
+        let mut scores: Vec<u128> = self.monkeys.iter().map(|m| m.total_inspections).collect();
+        scores.sort_by(|a, b| b.cmp(a));
+        scores[0] * scores[1]
+    }
+
+    pub fn throw_first(&mut self, monkey: usize) {
+        if let Some(mut worry) = self.monkeys[monkey].items.pop_front() {
+            worry = self.monkeys[monkey].op.eval_on(worry);
+            self.worry_div.map(|d| {
+                worry = ModNum::new(worry.a() / d, worry.m());
+            });
+            let test = ModNum::new(worry.a(), self.monkeys[monkey].div_test_value) == 0;
+            let target = if test {
+                self.monkeys[monkey].true_monkey
+            } else {
+                self.monkeys[monkey].false_monkey
+            };
+            self.monkeys[target].items.push_back(worry);
+            self.monkeys[monkey].total_inspections += 1;
+        }
+    }
+
+    pub fn throw_all(&mut self, monkey: usize) {
+        while !self.monkeys[monkey].items.is_empty() {
+            self.throw_first(monkey);
+        }
+    }
+
+    pub fn round(&mut self) {
+        for monkey in 0..self.monkeys.len() {
+            self.throw_all(monkey);
+        }
+    }
+}
+
+impl Solution for MonkeyTroop {
+    type Part1 = u128;
+    type Part2 = u128;
+
+    fn load(input: &Input) -> anyhow::Result<Self> {
+        Self::from_input(input, None)
+    }
+
+    fn part1(&self) -> anyhow::Result<u128> {
+        let troop = Self {
+            worry_div: Some(3),
+            ..self.clone()
+        };
+        Ok(evaluate(troop, 20))
+    }
+
+    fn part2(&self) -> anyhow::Result<u128> {
+        let troop = Self {
+            worry_div: None,
+            ..self.clone()
+        };
+        Ok(evaluate(troop, 10000))
+    }
+}
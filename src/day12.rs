@@ -0,0 +1,98 @@
+use std::cmp::min;
+
+use advent_code_lib::{
+    breadth_first_search, ContinueSearch, GridCharWorld, ParentMap, Position, SearchQueue,
+};
+
+use anyhow::bail;
+
+use crate::{input::Input, parse::check_grid, Solution};
+
+pub struct HeightMap {
+    map: GridCharWorld,
+}
+
+impl HeightMap {
+    pub fn from_file(filename: &str) -> anyhow::Result<Self> {
+        Self::from_input(&Input::from_file(filename)?)
+    }
+
+    pub fn from_input(input: &Input) -> anyhow::Result<Self> {
+        check_grid(input, "a height from a to z, S or E", |c| {
+            c.is_ascii_lowercase() || c == 'S' || c == 'E'
+        })?;
+        let map = input.text().parse::<GridCharWorld>()?;
+        for marker in ['S', 'E'] {
+            let count = map.positions_for(marker).len();
+            if count != 1 {
+                bail!("{}: Expected one {marker}, found {count}", input.name());
+            }
+        }
+        Ok(Self { map })
+    }
+}
+
+impl Solution for HeightMap {
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn load(input: &Input) -> anyhow::Result<Self> {
+        Self::from_input(input)
+    }
+
+    fn part1(&self) -> anyhow::Result<usize> {
+        Ok(part1(&self.map))
+    }
+
+    fn part2(&self) -> anyhow::Result<usize> {
+        Ok(part2(&self.map))
+    }
+}
+
+pub fn height_of(c: char) -> char {
+    match c {
+        'S' => 'a',
+        'E' => 'z',
+        _ => c,
+    }
+}
+
+pub fn part1(map: &GridCharWorld) -> usize {
+    distance_to_goal_from(map, map.any_position_for('S')).unwrap()
+}
+
+pub fn climb_distance(start: char, end: char) -> i8 {
+    end as i8 - start as i8
+}
+
+pub fn distance_to_goal_from(map: &GridCharWorld, start: Position) -> Option<usize> {
+    let end = map.any_position_for('E');
+    let parents: ParentMap<Position> = breadth_first_search(&start, |p, q| {
+        let p_height = height_of(map.value(*p).unwrap());
+        for neighbor in p.manhattan_neighbors() {
+            let distance = map
+                .value(neighbor)
+                .map(|h| climb_distance(p_height, height_of(h)));
+            if let Some(distance) = distance {
+                if distance <= 1 {
+                    q.enqueue(&neighbor);
+                }
+            }
+        }
+        ContinueSearch::Yes
+    });
+    parents
+        .path_back_from(&end)
+        .map(|path_back| path_back.len() - 1)
+}
+
+pub fn part2(map: &GridCharWorld) -> usize {
+    min(
+        part1(map),
+        map.positions_for('a')
+            .iter()
+            .filter_map(|start| distance_to_goal_from(map, *start))
+            .min()
+            .unwrap(),
+    )
+}
@@ -0,0 +1,230 @@
+use std::cmp::{min, Ordering};
+use std::fmt::Display;
+use std::iter::Peekable;
+use std::str::{CharIndices, FromStr};
+
+use crate::{
+    input::Input,
+    parse::{LineError, LineResult, NumberedLines},
+    Solution,
+};
+
+#[derive(Clone, Debug)]
+pub struct PacketPairs {
+    pairs: Vec<Vec<List>>,
+}
+
+impl PacketPairs {
+    pub fn from_file(filename: &str) -> anyhow::Result<Self> {
+        Self::from_input(&Input::from_file(filename)?)
+    }
+
+    pub fn from_input(input: &Input) -> anyhow::Result<Self> {
+        let mut pairs = vec![];
+        let mut lines = NumberedLines::new(input);
+        while !lines.at_end() {
+            let mut pair = vec![];
+            for _ in 0..2 {
+                pair.push(lines.parse_next("a packet", |line| line.parse())?);
+            }
+            pairs.push(pair);
+            lines.skip_blank()?;
+        }
+        Ok(Self { pairs })
+    }
+}
+
+impl Solution for PacketPairs {
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn load(input: &Input) -> anyhow::Result<Self> {
+        Self::from_input(input)
+    }
+
+    fn part1(&self) -> anyhow::Result<usize> {
+        Ok(part1(&self.pairs))
+    }
+
+    fn part2(&self) -> anyhow::Result<usize> {
+        Ok(part2(&self.pairs))
+    }
+}
+
+pub fn part1(pairs: &Vec<Vec<List>>) -> usize {
+    let mut index_total = 0;
+    for (i, pair) in pairs.iter().enumerate() {
+        if pair[0] < pair[1] {
+            index_total += i + 1;
+        }
+    }
+    index_total
+}
+
+pub fn part2(pairs: &Vec<Vec<List>>) -> usize {
+    let mut flattened = vec![];
+    for pair in pairs.iter() {
+        for item in pair.iter() {
+            flattened.push(item.clone());
+        }
+    }
+    let dividers: Vec<List> = ["[[2]]", "[[6]]"]
+        .iter()
+        .map(|s| s.parse().unwrap())
+        .collect();
+    dividers
+        .iter()
+        .for_each(|divider| flattened.push(divider.clone()));
+    flattened.sort();
+    dividers
+        .iter()
+        .map(|divider| divider_index_in(&flattened, divider))
+        .product()
+}
+
+pub fn divider_index_in(sorted: &Vec<List>, divider: &List) -> usize {
+    sorted
+        .iter()
+        .enumerate()
+        .find(|(_, v)| **v == *divider)
+        .map(|(i, _)| i)
+        .unwrap()
+        + 1
+}
+
+#[derive(Eq, PartialEq, Ord, Debug, Clone)]
+pub enum List {
+    Value(i64),
+    Values(Vec<List>),
+}
+
+impl PartialOrd for List {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        if let Some((i1, i2)) = self
+            .integer()
+            .and_then(|i1| other.integer().map(|i2| (i1, i2)))
+        {
+            i1.partial_cmp(&i2)
+        } else {
+            let l1 = self.list();
+            let l2 = other.list();
+            let len1 = l1.len();
+            let len2 = l2.len();
+            for i in 0..min(len1, len2) {
+                match l1[i].partial_cmp(&l2[i]).unwrap() {
+                    Ordering::Equal => {}
+                    ordering => return Some(ordering),
+                }
+            }
+            len1.partial_cmp(&len2)
+        }
+    }
+}
+
+impl Display for List {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Value(v) => write!(f, "{}", *v),
+            Self::Values(vs) => {
+                let mut vs = vs.iter();
+                let first = match vs.next() {
+                    None => "".to_owned(),
+                    Some(v) => format!("{}", *v),
+                };
+                write!(f, "[{}", first)?;
+                loop {
+                    match vs.next() {
+                        None => return write!(f, "]"),
+                        Some(v) => write!(f, ",{}", *v)?,
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl FromStr for List {
+    type Err = LineError;
+
+    fn from_str(s: &str) -> LineResult<Self> {
+        let mut chars = s.char_indices().peekable();
+        let list = Self::recursive_parse(&mut chars, s.len())?;
+        match chars.next() {
+            None => Ok(list),
+            Some((i, c)) => Err(LineError::new(i + 1, "end of line", c.to_string().as_str())),
+        }
+    }
+}
+
+fn unexpected(chars: &mut Peekable<CharIndices>, end: usize, expected: &str) -> LineError {
+    match chars.peek() {
+        None => LineError::new(end + 1, expected, "end of line"),
+        Some((i, c)) => LineError::new(i + 1, expected, c.to_string().as_str()),
+    }
+}
+
+impl List {
+    pub fn integer(&self) -> Option<i64> {
+        if let Self::Value(v) = self {
+            Some(*v)
+        } else {
+            None
+        }
+    }
+
+    pub fn list(&self) -> Vec<Self> {
+        match self {
+            List::Value(v) => vec![List::Value(*v)],
+            List::Values(vs) => vs.clone(),
+        }
+    }
+
+    fn recursive_parse(chars: &mut Peekable<CharIndices>, end: usize) -> LineResult<Self> {
+        match chars.peek() {
+            Some((_, c)) if c.is_ascii_digit() => Self::parse_number(chars),
+            Some((_, '[')) => Self::parse_list(chars, end),
+            _ => Err(unexpected(chars, end, "a number or '['")),
+        }
+    }
+
+    fn parse_number(chars: &mut Peekable<CharIndices>) -> LineResult<Self> {
+        let mut number = String::new();
+        let mut column = 0;
+        while let Some((i, digit)) = chars.peek().copied() {
+            if !digit.is_ascii_digit() {
+                break;
+            }
+            if number.is_empty() {
+                column = i + 1;
+            }
+            number.push(digit);
+            chars.next();
+        }
+        number
+            .parse()
+            .map(Self::Value)
+            .map_err(|_| LineError::new(column, "a 64-bit integer", number.as_str()))
+    }
+
+    fn parse_list(chars: &mut Peekable<CharIndices>, end: usize) -> LineResult<Self> {
+        chars.next();
+        let mut list = vec![];
+        if let Some((_, ']')) = chars.peek() {
+            chars.next();
+            return Ok(Self::Values(list));
+        }
+        loop {
+            list.push(Self::recursive_parse(chars, end)?);
+            match chars.peek() {
+                Some((_, ']')) => {
+                    chars.next();
+                    return Ok(Self::Values(list));
+                }
+                Some((_, ',')) => {
+                    chars.next();
+                }
+                _ => return Err(unexpected(chars, end, "',' or ']'")),
+            }
+        }
+    }
+}
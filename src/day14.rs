@@ -0,0 +1,228 @@
+use std::{
+    cmp::{max, min},
+    fmt::Display,
+};
+
+use advent_code_lib::{Dir, InfiniteGrid, Position};
+
+use crate::{
+    input::Input,
+    parse::{parse_lines, parse_token, Fields, LineError, LineResult},
+    simulation::Simulation,
+    Solution,
+};
+
+pub fn count_sand(mut rocks: RockSection) -> usize {
+    rocks.pour_sand_until_full();
+    rocks.sand_count
+}
+
+#[derive(Default, Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Contents {
+    #[default]
+    Air,
+    Sand,
+    Rock,
+}
+
+impl Display for Contents {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let c = match self {
+            Self::Air => ".",
+            Self::Rock => "#",
+            Self::Sand => "o",
+        };
+        write!(f, "{c}")
+    }
+}
+
+#[derive(Default, Clone, Debug)]
+pub struct RockSection {
+    cells: InfiniteGrid<Contents>,
+    sand_count: usize,
+    floor_level: Option<isize>,
+}
+
+impl Display for RockSection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.cells)
+    }
+}
+
+fn pair_from(column: usize, s: &str) -> LineResult<(isize, isize)> {
+    let (x, y) = s
+        .split_once(',')
+        .ok_or_else(|| LineError::new(column, "a point like 498,4", s))?;
+    let x = parse_token(column, x, "an x coordinate")?;
+    let y = parse_token(column + s.len() - y.len(), y, "a y coordinate")?;
+    Ok((x, y))
+}
+
+impl RockSection {
+    pub fn from_file(filename: &str) -> anyhow::Result<Self> {
+        Self::from_input(&Input::from_file(filename)?)
+    }
+
+    pub fn from_input(input: &Input) -> anyhow::Result<Self> {
+        let mut result = Self::default();
+        parse_lines(input, |line| result.add_path(line))?;
+        Ok(result)
+    }
+
+    pub fn add_floor(&mut self) {
+        self.floor_level = Some(self.cells.max_y() + 2);
+    }
+
+    pub fn blocked(&self, p: Position) -> bool {
+        self.floor_level.map_or(false, |f| p.row >= f) || self.cells.get_pos(p) != Contents::Air
+    }
+
+    pub fn add_path(&mut self, path: &str) -> LineResult<()> {
+        let mut fields = Fields::new(path);
+        let (column, pair) = fields.next("a point")?;
+        let (mut x1, mut y1) = pair_from(column, pair)?;
+        while fields.end().is_err() {
+            fields.expect("->")?;
+            let (column, pair) = fields.next("a point")?;
+            let (x2, y2) = pair_from(column, pair)?;
+            if x2 == x1 {
+                self.add_from((min(y1, y2)..=max(y1, y2)).map(|y| (x1, y)));
+            } else if y2 == y1 {
+                self.add_from((min(x1, x2)..=max(x1, x2)).map(|x| (x, y1)));
+            } else {
+                let expected = format!("a point in line with {x1},{y1}");
+                return Err(LineError::new(column, expected.as_str(), pair));
+            }
+            x1 = x2;
+            y1 = y2;
+        }
+        Ok(())
+    }
+
+    fn add_from<I: Iterator<Item = (isize, isize)>>(&mut self, cells: I) {
+        for (x, y) in cells {
+            self.cells.add(x, y, Contents::Rock);
+        }
+    }
+
+    pub fn add_sand(&mut self) {
+        let mut sand_pos = Position { col: 500, row: 0 };
+        if self.blocked(sand_pos) {
+            return;
+        }
+        let bottom = match self.floor_level {
+            None => Some(self.cells.max_y()),
+            Some(_) => None,
+        };
+        loop {
+            match self.next_sand_move(sand_pos) {
+                Some(updated) => {
+                    if bottom.map_or(false, |bottom| updated.row > bottom) {
+                        return;
+                    } else {
+                        sand_pos = updated;
+                    }
+                }
+                None => {
+                    self.cells.add_pos(sand_pos, Contents::Sand);
+                    self.sand_count += 1;
+                    return;
+                }
+            }
+        }
+    }
+
+    pub fn pour_sand_until_full(&mut self) {
+        while self.step() {}
+    }
+
+    pub fn next_sand_move(&self, sand_pos: Position) -> Option<Position> {
+        [Dir::S, Dir::Sw, Dir::Se]
+            .iter()
+            .map(|d| sand_pos.updated(*d))
+            .find(|p| !self.blocked(*p))
+    }
+}
+
+impl Simulation for RockSection {
+    /// Pours one unit of sand, until one fails to come to rest.
+    fn step(&mut self) -> bool {
+        let last_count = self.sand_count;
+        self.add_sand();
+        self.sand_count > last_count
+    }
+
+    fn snapshot(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl Solution for RockSection {
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn load(input: &Input) -> anyhow::Result<Self> {
+        Self::from_input(input)
+    }
+
+    fn part1(&self) -> anyhow::Result<usize> {
+        Ok(count_sand(self.clone()))
+    }
+
+    fn part2(&self) -> anyhow::Result<usize> {
+        let mut rocks = self.clone();
+        rocks.add_floor();
+        Ok(count_sand(rocks))
+    }
+
+    fn drawing(&self) -> Option<String> {
+        let mut rocks = self.clone();
+        rocks.pour_sand_until_full();
+        Some(rocks.to_string())
+    }
+
+    fn simulation(&self) -> Option<Box<dyn Simulation>> {
+        Some(Box::new(self.clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::day14::RockSection;
+
+    #[test]
+    pub fn test1() {
+        let mut rocks = RockSection::from_file("ex/day14.txt").unwrap();
+        rocks.pour_sand_until_full();
+        let expected = "......o...
+.....ooo..
+....#ooo##
+...o#ooo#.
+..###ooo#.
+....oooo#.
+.o.ooooo#.
+#########.
+";
+        assert_eq!(expected, format!("{rocks}"));
+    }
+
+    #[test]
+    pub fn test2() {
+        let mut rocks = RockSection::from_file("ex/day14.txt").unwrap();
+        rocks.add_floor();
+        rocks.pour_sand_until_full();
+        let expected = "..........o..........
+.........ooo.........
+........ooooo........
+.......ooooooo.......
+......oo#ooo##o......
+.....ooo#ooo#ooo.....
+....oo###ooo#oooo....
+...oooo.oooo#ooooo...
+..oooooooooo#oooooo..
+.ooo#########ooooooo.
+ooooo.......ooooooooo
+";
+        assert_eq!(expected, format!("{rocks}"));
+    }
+}
@@ -0,0 +1,341 @@
+use std::{
+    collections::{BTreeMap, BTreeSet, BinaryHeap},
+    fmt::Display,
+    iter::repeat,
+};
+
+use advent_code_lib::{breadth_first_search, search, ContinueSearch, SearchQueue};
+use anyhow::bail;
+
+use crate::{
+    input::Input,
+    parse::{parse_lines, Fields, LineError},
+    Solution,
+};
+
+pub fn part1(tunnels: &TunnelGraph) -> usize {
+    part1_search(tunnels).best
+}
+
+pub fn part2(tunnels: &TunnelGraph) -> usize {
+    let start = PressureNode::start_at(tunnels, 26, true);
+    conduct_search(tunnels, start).best
+}
+
+fn part1_search(tunnels: &TunnelGraph) -> PressureSearch {
+    let start = PressureNode::start_at(tunnels, 30, false);
+    conduct_search(tunnels, start)
+}
+
+/// The most pressure released, and how much searching it took.
+#[derive(Copy, Clone, Debug)]
+pub struct PressureSearch {
+    pub best: usize,
+    pub enqueued: usize,
+    pub dequeued: usize,
+}
+
+fn conduct_search(tunnels: &TunnelGraph, start: PressureNode) -> PressureSearch {
+    let mut best = 0;
+    let mut visited = BTreeSet::new();
+    let mut queue = PressureQueue::new();
+    queue.enqueue(&start);
+
+    let result = search(queue, |s, q| {
+        let options = s.successors(tunnels);
+        let potential = potential(tunnels, s.min_minutes_left(), &options);
+        if s.total_pressure + potential >= best {
+            for i in 0..s.explorers.len() {
+                for successor in options.iter() {
+                    if let Some(node) = s.successor(i, *successor, tunnels) {
+                        if !visited.contains(&node) {
+                            visited.insert(node.clone());
+                            if node.total_pressure > best {
+                                best = node.total_pressure;
+                            }
+                            q.enqueue(&node);
+                        }
+                    }
+                }
+            }
+        }
+        ContinueSearch::Yes
+    });
+    PressureSearch {
+        best,
+        enqueued: result.enqueued(),
+        dequeued: result.dequeued(),
+    }
+}
+
+fn potential(tunnels: &TunnelGraph, minutes_left: usize, remaining_nodes: &Vec<usize>) -> usize {
+    let mut values: Vec<usize> = remaining_nodes
+        .iter()
+        .map(|n| tunnels.pressure_for(*n))
+        .collect();
+    values.sort_by(|a, b| b.cmp(a));
+    while values.len() > minutes_left {
+        values.pop();
+    }
+    values.iter().sum::<usize>() * minutes_left
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
+struct ExplorerState {
+    minutes_left: usize,
+    at: usize,
+}
+
+#[derive(Default, Clone, Eq, PartialEq, Ord, Debug)]
+struct PressureNode {
+    explorers: Vec<ExplorerState>,
+    opened: Vec<usize>,
+    total_pressure: usize,
+}
+
+impl PressureNode {
+    fn start_at(tunnels: &TunnelGraph, minutes_left: usize, elephant_help: bool) -> Self {
+        let start = tunnels.start_valve();
+        let mut result = Self::default();
+        result.explorers.push(ExplorerState {
+            at: start,
+            minutes_left,
+        });
+        if elephant_help {
+            result.explorers.push(ExplorerState {
+                at: start,
+                minutes_left,
+            });
+        }
+        result.opened = repeat(0).take(tunnels.names.len()).collect();
+        result
+    }
+
+    fn min_minutes_left(&self) -> usize {
+        self.explorers
+            .iter()
+            .map(|ex| ex.minutes_left)
+            .min()
+            .unwrap()
+    }
+
+    fn successor(
+        &self,
+        explorer: usize,
+        valve: usize,
+        tunnels: &TunnelGraph,
+    ) -> Option<PressureNode> {
+        let moves = tunnels.valve_activation_times[self.explorers[explorer].at][valve];
+        if moves <= self.explorers[explorer].minutes_left {
+            let mut opened = self.opened.clone();
+            let pressure_from =
+                (self.explorers[explorer].minutes_left - moves) * tunnels.pressure_for(valve);
+            opened[valve] = pressure_from;
+            let mut updated_explorers = self.explorers.clone();
+            updated_explorers[explorer].at = valve;
+            updated_explorers[explorer].minutes_left -= moves;
+            Some(PressureNode {
+                explorers: updated_explorers,
+                opened,
+                total_pressure: self.total_pressure + pressure_from,
+            })
+        } else {
+            None
+        }
+    }
+
+    fn successors(&self, tunnels: &TunnelGraph) -> Vec<usize> {
+        tunnels
+            .valves()
+            .filter(|valve| self.opened[*valve] == 0 && tunnels.pressure_for(*valve) > 0)
+            .collect()
+    }
+}
+
+impl PartialOrd for PressureNode {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        match self.total_pressure.partial_cmp(&other.total_pressure) {
+            Some(core::cmp::Ordering::Equal) => {}
+            ord => return ord,
+        }
+        self.explorers.partial_cmp(&other.explorers)
+    }
+}
+
+#[derive(Default)]
+struct PressureQueue {
+    heap: BinaryHeap<PressureNode>,
+}
+
+impl SearchQueue<PressureNode> for PressureQueue {
+    fn new() -> Self {
+        Self::default()
+    }
+
+    fn enqueue(&mut self, item: &PressureNode) {
+        self.heap.push(item.clone());
+    }
+
+    fn dequeue(&mut self) -> Option<PressureNode> {
+        self.heap.pop()
+    }
+
+    fn len(&self) -> usize {
+        self.heap.len()
+    }
+}
+
+#[derive(Default, Clone, Debug)]
+pub struct TunnelGraph {
+    names: Vec<String>,
+    ids: BTreeMap<String, usize>,
+    valve2flow: Vec<usize>,
+    valve2tunnels: Vec<Vec<usize>>,
+    valve_activation_times: Vec<Vec<usize>>,
+}
+
+impl Display for TunnelGraph {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for valve in self.valves() {
+            let out = format!("{:?}", self.valve2tunnels.get(valve).unwrap())
+                .replace("[", "")
+                .replace("]", "")
+                .replace('"', "");
+            write!(
+                f,
+                "Valve {} has flow rate={}; tunnels lead to valves {}\n",
+                valve,
+                self.pressure_for(valve),
+                out
+            )?
+        }
+        Ok(())
+    }
+}
+
+impl TunnelGraph {
+    pub fn from_file(filename: &str) -> anyhow::Result<Self> {
+        Self::from_input(&Input::from_file(filename)?)
+    }
+
+    pub fn from_input(input: &Input) -> anyhow::Result<Self> {
+        let mut result = TunnelGraph::default();
+        let lines = parse_lines(input, |line| {
+            let mut fields = Fields::split(line, &[' ', ',']);
+            fields.expect("Valve")?;
+            let (_, name) = fields.next("a valve name")?;
+            fields.expect("has")?;
+            fields.expect("flow")?;
+            let rate = fields.parse_between("rate=", ";", "a flow rate")?;
+            for choices in [
+                ["tunnels", "tunnel"],
+                ["lead", "leads"],
+                ["to", "to"],
+                ["valves", "valve"],
+            ] {
+                let expected = format!("{:?}", choices[0]);
+                let (column, word) = fields.next(expected.as_str())?;
+                if !choices.contains(&word) {
+                    return Err(LineError::new(column, expected.as_str(), word));
+                }
+            }
+            let tunnels = fields.rest();
+            if tunnels.is_empty() {
+                fields.next("a valve name")?;
+            }
+            let tunnels = tunnels
+                .iter()
+                .map(|(column, tunnel)| (*column, tunnel.to_string()))
+                .collect::<Vec<_>>();
+
+            let id_num = result.names.len();
+            result.ids.insert(name.to_string(), id_num);
+            result.names.push(name.to_string());
+            result.valve2flow.push(rate);
+            Ok(tunnels)
+        })?;
+        for (i, tunnels) in lines.iter().enumerate() {
+            let tunnels = tunnels
+                .iter()
+                .map(|(column, s)| {
+                    result.ids.get(s).copied().ok_or_else(|| {
+                        LineError::new(*column, "a listed valve", s).at(input.name(), i + 1)
+                    })
+                })
+                .collect::<Result<_, _>>()?;
+            result.valve2tunnels.push(tunnels);
+        }
+        if !result.ids.contains_key("AA") {
+            bail!("{}: No valve AA to start from", input.name());
+        }
+        result.valve_activation_times = result
+            .valves()
+            .map(|valve| result.activation_times_from(valve))
+            .collect();
+        Ok(result)
+    }
+
+    pub fn start_valve(&self) -> usize {
+        self.ids.get("AA").copied().unwrap()
+    }
+
+    pub fn pressure_for(&self, valve: usize) -> usize {
+        self.valve2flow[valve]
+    }
+
+    pub fn valves(&self) -> impl Iterator<Item = usize> {
+        0..self.valve2flow.len()
+    }
+
+    fn activation_times_from(&self, src: usize) -> Vec<usize> {
+        let parents = breadth_first_search(&src, |s, q| {
+            for neighbor in self.valve2tunnels[*s].iter() {
+                q.enqueue(neighbor);
+            }
+            ContinueSearch::Yes
+        });
+
+        self.valves()
+            .filter_map(|valve| parents.path_back_from(&valve).map(|p| p.len()))
+            .collect()
+    }
+}
+
+impl Solution for TunnelGraph {
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn load(input: &Input) -> anyhow::Result<Self> {
+        Self::from_input(input)
+    }
+
+    fn part1(&self) -> anyhow::Result<usize> {
+        Ok(part1(self))
+    }
+
+    fn part2(&self) -> anyhow::Result<usize> {
+        Ok(part2(self))
+    }
+
+    fn diagnostics(&self) -> Vec<(String, String)> {
+        let search = part1_search(self);
+        vec![
+            ("part 1 enqueued".to_owned(), search.enqueued.to_string()),
+            ("part 1 dequeued".to_owned(), search.dequeued.to_string()),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::day16::TunnelGraph;
+
+    #[test]
+    pub fn test() {
+        let tunnels = TunnelGraph::from_file("ex/day16.txt").unwrap();
+        assert_eq!(
+            tunnels.activation_times_from(tunnels.start_valve()),
+            vec![1, 2, 3, 2, 3, 4, 5, 6, 2, 3]
+        );
+    }
+}
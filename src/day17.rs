@@ -0,0 +1,512 @@
+use advent_code_lib::Position;
+use anyhow::bail;
+use bare_metal_modulo::*;
+use enum_iterator::{all, Sequence};
+use std::{
+    cmp::{max, min},
+    fmt::{Debug, Display},
+};
+
+use crate::{
+    cycle::{Cycle, CycleDetector, Cyclic},
+    input::Input,
+    parse::{first_line, LineError},
+    simulation::Simulation,
+    Solution,
+};
+
+const WELL_WIDTH: usize = 7;
+
+const PART_1_ITERATIONS: isize = 2022;
+const PART_2_ITERATIONS: usize = 1000000000000;
+
+/// How far below the top of the tower the fingerprint looks. Air deeper than
+/// this is assumed never to be reached again, which is only a heuristic, as
+/// an open shaft can run deeper; `CycleDetector` checks each cycle it finds
+/// against the next one before trusting it.
+const FINGERPRINT_ROWS: usize = 64;
+
+#[derive(Clone, Debug)]
+pub struct JetPattern {
+    move_line: String,
+}
+
+impl JetPattern {
+    pub fn from_file(filename: &str) -> anyhow::Result<Self> {
+        Self::from_input(&Input::from_file(filename)?)
+    }
+
+    pub fn from_input(input: &Input) -> anyhow::Result<Self> {
+        Ok(Self {
+            move_line: read_moves(input)?,
+        })
+    }
+}
+
+impl Solution for JetPattern {
+    type Part1 = isize;
+    type Part2 = isize;
+
+    fn load(input: &Input) -> anyhow::Result<Self> {
+        Self::from_input(input)
+    }
+
+    fn part1(&self) -> anyhow::Result<isize> {
+        part1(self.move_line.as_str())
+    }
+
+    fn part2(&self) -> anyhow::Result<isize> {
+        let cycle = Tetris::find_cycle(self.move_line.as_str())?;
+        Ok(cycle.metric_at(PART_2_ITERATIONS) as isize)
+    }
+
+    fn diagnostics(&self) -> Vec<(String, String)> {
+        match Tetris::find_cycle(self.move_line.as_str()) {
+            Ok(cycle) => vec![
+                ("start drops".to_owned(), format!("{}", cycle.start)),
+                ("repetition drops".to_owned(), format!("{}", cycle.length)),
+                ("repetition length".to_owned(), format!("{}", cycle.gain())),
+            ],
+            Err(_) => vec![],
+        }
+    }
+
+    fn drawing(&self) -> Option<String> {
+        let tetris = Tetris::build_to_limit(self.move_line.as_str(), PART_1_ITERATIONS).ok()?;
+        Some(tetris.well.to_string())
+    }
+
+    fn simulation(&self) -> Option<Box<dyn Simulation>> {
+        let tetris = Tetris::from_moves(self.move_line.as_str()).ok()?;
+        Some(Box::new(tetris))
+    }
+}
+
+pub fn part1(move_line: &str) -> anyhow::Result<isize> {
+    Tetris::limit_solver(move_line, PART_1_ITERATIONS)
+}
+
+#[derive(Clone)]
+pub struct Tracker<T> {
+    items: Vec<T>,
+    track: ModNum<usize>,
+}
+
+impl<T: Copy> Tracker<T> {
+    pub fn new<I: Iterator<Item = T>>(items: I) -> Self {
+        let items: Vec<T> = items.collect();
+        let track = ModNum::new(0, items.len());
+        Self { items, track }
+    }
+
+    pub fn get(&self) -> T {
+        self.items[self.track.a()]
+    }
+
+    pub fn i(&self) -> usize {
+        self.track.a()
+    }
+
+    pub fn advance(&mut self) {
+        self.track += 1;
+    }
+}
+
+#[derive(Clone)]
+pub struct Tetris {
+    well: Well,
+    moves: Tracker<Move>,
+    pieces: Tracker<Tetromino>,
+}
+
+impl Tetris {
+    pub fn build_to_limit(move_line: &str, iterations: isize) -> anyhow::Result<Self> {
+        let mut tetris = Self::from_moves(move_line)?;
+        for _ in 0..iterations {
+            tetris.drop_next();
+        }
+        Ok(tetris)
+    }
+
+    pub fn limit_solver(move_line: &str, iterations: isize) -> anyhow::Result<isize> {
+        Ok(Self::build_to_limit(move_line, iterations)?.height())
+    }
+
+    /// Finds where the tower starts repeating, to work out its height after
+    /// any number of drops.
+    pub fn find_cycle(move_line: &str) -> anyhow::Result<Cycle> {
+        CycleDetector::default().find(&Self::from_moves(move_line)?)
+    }
+
+    pub fn height(&self) -> isize {
+        self.well.height()
+    }
+
+    pub fn next_piece(&self) -> Tetromino {
+        self.pieces.get()
+    }
+
+    pub fn from_file(filename: &str) -> anyhow::Result<Self> {
+        Self::from_input(&Input::from_file(filename)?)
+    }
+
+    pub fn from_input(input: &Input) -> anyhow::Result<Self> {
+        Self::from_moves(read_moves(input)?.as_str())
+    }
+
+    pub fn from_moves(move_line: &str) -> anyhow::Result<Self> {
+        let moves = Tracker::new(moves_from(move_line)?.into_iter());
+        let pieces = Tracker::new(all::<Tetromino>());
+        Ok(Self {
+            well: Well::default(),
+            moves,
+            pieces,
+        })
+    }
+
+    pub fn drop_next(&mut self) {
+        self.well.drop_into(self.pieces.get(), &mut self.moves);
+        self.pieces.advance();
+    }
+}
+
+impl Cyclic for Tetris {
+    type Fingerprint = (usize, usize, Vec<(usize, usize)>);
+
+    fn advance(&mut self) {
+        self.drop_next();
+    }
+
+    /// Rocks only ever enter the surface air, so it decides what happens next.
+    fn fingerprint(&self) -> Self::Fingerprint {
+        (
+            self.moves.i(),
+            self.pieces.i(),
+            self.well.surface(FINGERPRINT_ROWS),
+        )
+    }
+
+    fn metric(&self) -> i64 {
+        self.height() as i64
+    }
+}
+
+impl Simulation for Tetris {
+    /// Drops one rock; the tower never stops growing.
+    fn step(&mut self) -> bool {
+        self.drop_next();
+        true
+    }
+
+    fn snapshot(&self) -> String {
+        self.well.to_string()
+    }
+}
+
+pub fn read_moves(input: &Input) -> anyhow::Result<String> {
+    let line = first_line(input, "a line of jets")?.to_owned();
+    let bad = line.char_indices().find(|(_, c)| !matches!(c, '<' | '>'));
+    match bad {
+        None if !line.is_empty() => Ok(line),
+        None => Err(LineError::new(1, "< or >", "end of line")
+            .at(input.name(), 1)
+            .into()),
+        Some((i, c)) => Err(LineError::new(i + 1, "< or >", c.to_string().as_str())
+            .at(input.name(), 1)
+            .into()),
+    }
+}
+
+/// The jets in `s`, of which there must be at least one.
+pub fn moves_from(s: &str) -> anyhow::Result<Vec<Move>> {
+    if s.is_empty() {
+        bail!("No jets");
+    }
+    s.chars().map(Move::try_from).collect()
+}
+
+#[derive(Copy, Clone, Default, Eq, PartialEq, Debug, Hash)]
+pub enum WellCell {
+    Rock,
+    #[default]
+    Air,
+}
+
+impl Display for WellCell {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let c = match self {
+            WellCell::Rock => "#",
+            WellCell::Air => ".",
+        };
+        write!(f, "{c}")
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct Well {
+    cells: Vec<[WellCell; WELL_WIDTH]>,
+}
+
+impl Display for Well {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for i in (0..self.cells.len()).rev() {
+            write!(f, "|")?;
+            for c in self.cells[i].iter() {
+                write!(f, "{c}")?;
+            }
+            writeln!(f, "|")?;
+        }
+        write!(f, "+")?;
+        for _ in 0..WELL_WIDTH {
+            write!(f, "-")?;
+        }
+        writeln!(f, "+")
+    }
+}
+
+impl Well {
+    pub fn at(&self, p: Position) -> WellCell {
+        if p.row >= self.height() {
+            WellCell::Air
+        } else {
+            self.cells[p.row as usize][p.col as usize]
+        }
+    }
+
+    /// The air within `max_depth` rows of the top that can be reached from
+    /// above the tower by moving down, left and right, as (depth below the
+    /// top, column), in order. As rocks only move that way, no rock can reach
+    /// anywhere else.
+    pub fn surface(&self, max_depth: usize) -> Vec<(usize, usize)> {
+        let height = self.cells.len();
+        let floor = height.saturating_sub(max_depth);
+        let mut reached = vec![[false; WELL_WIDTH]; height - floor];
+        let mut pending = (0..WELL_WIDTH).map(|col| (height, col)).collect::<Vec<_>>();
+        while let Some((row, col)) = pending.pop() {
+            let mut next = vec![];
+            if row > floor {
+                next.push((row - 1, col));
+            }
+            if row < height {
+                if col > 0 {
+                    next.push((row, col - 1));
+                }
+                if col + 1 < WELL_WIDTH {
+                    next.push((row, col + 1));
+                }
+            }
+            for (r, c) in next {
+                if self.cells[r][c] == WellCell::Air && !reached[r - floor][c] {
+                    reached[r - floor][c] = true;
+                    pending.push((r, c));
+                }
+            }
+        }
+        let mut surface = vec![];
+        for (row, cols) in reached.iter().enumerate().rev() {
+            for (col, reached) in cols.iter().enumerate() {
+                if *reached {
+                    surface.push((height - floor - row, col));
+                }
+            }
+        }
+        surface
+    }
+
+    pub fn row(&self, height: isize) -> [WellCell; WELL_WIDTH] {
+        self.cells[height as usize].clone()
+    }
+
+    pub fn height(&self) -> isize {
+        self.cells.len() as isize
+    }
+
+    pub fn contacts(&self, t: Tetromino, p: Position) -> bool {
+        p.row < 0
+            || t.positions(p)
+                .filter(|tp| tp.row < self.height())
+                .any(|tp| {
+                    self.cells[tp.row as usize]
+                        .iter()
+                        .enumerate()
+                        .any(|(i, c)| *c == WellCell::Rock && i as isize == tp.col)
+                })
+    }
+
+    pub fn drop_into(&mut self, t: Tetromino, moves: &mut Tracker<Move>) {
+        let mut tp = Position {
+            col: 2,
+            row: self.height() + 3,
+        };
+        loop {
+            if let Some(new_tp) = self.push(t, tp, moves.get()) {
+                tp = new_tp;
+            }
+            moves.advance();
+            if self.contacts(t, tp - Position { row: 1, col: 0 }) {
+                break;
+            }
+            tp.row -= 1;
+        }
+        for rock in t.positions(tp) {
+            while rock.row >= self.height() {
+                self.cells.push([WellCell::Air; WELL_WIDTH]);
+            }
+            self.cells[rock.row as usize][rock.col as usize] = WellCell::Rock;
+        }
+    }
+
+    fn push(&self, t: Tetromino, tp: Position, m: Move) -> Option<Position> {
+        let new_tp = m.push(tp, t.width());
+        if t.positions(new_tp).any(|p| self.at(p) == WellCell::Rock) {
+            None
+        } else {
+            Some(new_tp)
+        }
+    }
+
+    pub fn add_row(&mut self, chars: &str) {
+        self.cells.push([WellCell::Air; WELL_WIDTH]);
+        for (i, c) in chars.chars().enumerate() {
+            if c == '#' {
+                self.cells.last_mut().unwrap()[i] = WellCell::Rock;
+            }
+        }
+    }
+}
+
+#[derive(Copy, Clone, Sequence, Eq, PartialEq, Debug)]
+pub enum Tetromino {
+    Minus,
+    Plus,
+    ReverseL,
+    Or,
+    Square,
+}
+
+impl Tetromino {
+    pub fn positions(&self, bottom_left: Position) -> impl Iterator<Item = Position> {
+        match self {
+            Tetromino::Minus => [(0, 0), (1, 0), (2, 0), (3, 0)].iter(),
+            Tetromino::Plus => [(1, 0), (1, 1), (0, 1), (2, 1), (1, 2)].iter(),
+            Tetromino::ReverseL => [(0, 0), (1, 0), (2, 0), (2, 1), (2, 2)].iter(),
+            Tetromino::Or => [(0, 0), (0, 1), (0, 2), (0, 3)].iter(),
+            Tetromino::Square => [(0, 0), (0, 1), (1, 0), (1, 1)].iter(),
+        }
+        .map(move |(x, y)| bottom_left + Position { col: *x, row: *y })
+    }
+
+    pub fn width(&self) -> isize {
+        self.positions(Position { col: 1, row: 0 })
+            .map(|p| p.col)
+            .max()
+            .unwrap()
+    }
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum Move {
+    Left,
+    Right,
+}
+
+impl Move {
+    pub fn push(&self, p: Position, width: isize) -> Position {
+        match self {
+            Self::Left => Position {
+                row: p.row,
+                col: max(0, p.col - 1),
+            },
+            Self::Right => Position {
+                row: p.row,
+                col: min(WELL_WIDTH as isize - width, p.col + 1),
+            },
+        }
+    }
+}
+
+impl TryFrom<char> for Move {
+    type Error = anyhow::Error;
+
+    fn try_from(c: char) -> anyhow::Result<Self> {
+        match c {
+            '<' => Ok(Move::Left),
+            '>' => Ok(Move::Right),
+            _ => bail!("{c:?} is not a jet"),
+        }
+    }
+}
+
+impl Display for Move {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let c = match self {
+            Self::Left => '<',
+            Self::Right => '>',
+        };
+        write!(f, "{c}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use enum_iterator::all;
+
+    use crate::{
+        cycle::{CycleDetector, Method},
+        day17::{moves_from, part1, read_moves, Tetris, Tetromino, Tracker, Well},
+        input::Input,
+    };
+
+    #[test]
+    fn test_empty() {
+        let w = Well::default();
+        assert_eq!("+-------+\n", format!("{w}"));
+    }
+
+    const EX_1: &str = "|....##.|
+|....##.|
+|....#..|
+|..#.#..|
+|..#.#..|
+|#####..|
+|..###..|
+|...#...|
+|..####.|
++-------+
+";
+    #[test]
+    fn test_drop() {
+        let move_line = read_moves(&Input::from_file("ex/day17.txt").unwrap()).unwrap();
+        let mut moves = Tracker::new(moves_from(move_line.as_str()).unwrap().into_iter());
+        let mut w = Well::default();
+        for t in all::<Tetromino>() {
+            w.drop_into(t, &mut moves);
+        }
+        assert_eq!(format!("{w}"), EX_1);
+    }
+
+    #[test]
+    fn test_cycle() {
+        let move_line = read_moves(&Input::from_file("ex/day17.txt").unwrap()).unwrap();
+        let tetris = Tetris::from_moves(move_line.as_str()).unwrap();
+        for method in [Method::Table, Method::Brent, Method::Floyd] {
+            let detector = CycleDetector {
+                method,
+                ..CycleDetector::default()
+            };
+            let cycle = detector.find(&tetris).unwrap();
+            assert_eq!(cycle.length, 35, "{method:?}");
+            assert_eq!(cycle.metric_at(2022), 3068);
+            assert_eq!(cycle.metric_at(1000000000000), 1514285714288);
+        }
+    }
+    #[test]
+    fn test_bad_moves() {
+        assert_eq!(
+            Tetris::from_moves("<>x").err().unwrap().to_string(),
+            "'x' is not a jet"
+        );
+        assert!(Tetris::find_cycle("").is_err());
+        assert!(part1("<<^").is_err());
+    }
+}
@@ -0,0 +1,119 @@
+use std::{cmp::min, collections::BTreeSet};
+
+use advent_code_lib::{heuristic_search, Point};
+
+use crate::{
+    input::Input,
+    parse::{parse_lines, Fields},
+    Solution,
+};
+
+const CUBE_DIM: usize = 3;
+
+type Cubito = Point<i64, CUBE_DIM>;
+
+pub fn part1(droplet: &Droplet) -> usize {
+    droplet.surface_area(|_| true)
+}
+
+pub fn part2(droplet: &Droplet) -> usize {
+    droplet.surface_area(|cube| !droplet.trapped(cube))
+}
+
+pub struct Droplet {
+    cubes: BTreeSet<Cubito>,
+}
+
+impl Droplet {
+    pub fn from_file(filename: &str) -> anyhow::Result<Self> {
+        Self::from_input(&Input::from_file(filename)?)
+    }
+
+    pub fn from_input(input: &Input) -> anyhow::Result<Self> {
+        Ok(Self {
+            cubes: parse_lines(input, |line| {
+                let mut fields = Fields::split(line, &[',']);
+                let mut coords = [0; CUBE_DIM];
+                for coord in coords.iter_mut() {
+                    *coord = fields.parse("an integer coordinate")?;
+                }
+                fields.end()?;
+                Ok(Cubito::new(coords))
+            })?
+            .into_iter()
+            .collect(),
+        })
+    }
+
+    pub fn surface_area<I: Fn(&Cubito) -> bool>(&self, include: I) -> usize {
+        let mut result = 0;
+        for cube in self.cubes.iter() {
+            for neighbor in cube.manhattan_neighbors() {
+                if !self.cubes.contains(&neighbor) && include(&neighbor) {
+                    result += 1;
+                }
+            }
+        }
+        result
+    }
+
+    pub fn trapped(&self, cube: &Cubito) -> bool {
+        let (min_p, max_p) = Cubito::min_max_points(self.cubes.iter().copied()).unwrap();
+        let node_cost = |n: &Cubito| cube.manhattan_distance(n);
+        let at_goal = |n: &Cubito| {
+            n.values()
+                .enumerate()
+                .any(|(i, v)| v < min_p[i] || v > max_p[i])
+        };
+        let heuristic = |n: &Cubito| {
+            (0..3)
+                .map(|i| min((n[i] - min_p[i]).abs(), (n[i] - max_p[i]).abs()))
+                .min()
+                .unwrap()
+        };
+        let get_successors = |n: &Cubito| {
+            n.manhattan_neighbors()
+                .iter()
+                .copied()
+                .filter(|n| !self.cubes.contains(n))
+                .collect()
+        };
+        let result = heuristic_search(*cube, node_cost, at_goal, heuristic, get_successors);
+        !at_goal(&result.node_at_goal().unwrap())
+    }
+}
+
+impl Solution for Droplet {
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn load(input: &Input) -> anyhow::Result<Self> {
+        Self::from_input(input)
+    }
+
+    fn part1(&self) -> anyhow::Result<usize> {
+        Ok(part1(self))
+    }
+
+    fn part2(&self) -> anyhow::Result<usize> {
+        Ok(part2(self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::day18::{part1, Cubito, Droplet};
+
+    #[test]
+    fn test_1() {
+        let droplet = Droplet::from_file("ex/day18.txt").unwrap();
+        assert_eq!(part1(&droplet), 64);
+    }
+
+    #[test]
+    fn test_trapped() {
+        let droplet = Droplet::from_file("ex/day18.txt").unwrap();
+        assert!(droplet.trapped(&Cubito::new([2, 2, 5])));
+        assert!(!droplet.trapped(&Cubito::new([0, 2, 2])))
+    }
+}
@@ -0,0 +1,303 @@
+use std::{cmp::max, collections::HashSet, iter::zip};
+
+use enum_iterator::{all, reverse_all, Sequence};
+use enum_map::{Enum, EnumMap};
+
+use crate::{
+    input::Input,
+    parse::{parse_lines, Fields, LineResult},
+    Solution,
+};
+
+pub fn part1(costs: &Costs) -> usize {
+    costs.part_1_score(24)
+}
+
+pub fn part2(costs: &Costs) -> usize {
+    costs.part_2_score(32)
+}
+
+pub type Blueprint = EnumMap<Mineral, EnumMap<Mineral, usize>>;
+
+pub fn build_robot(
+    blueprint: &Blueprint,
+    robot: Mineral,
+    mined_minerals: &EnumMap<Mineral, usize>,
+) -> Option<EnumMap<Mineral, usize>> {
+    let mut result = mined_minerals.clone();
+    for (mineral, cost) in blueprint[robot].iter() {
+        if *cost > result[mineral] {
+            return None;
+        } else {
+            result[mineral] -= cost;
+        }
+    }
+    Some(result)
+}
+
+#[derive(Clone, Debug)]
+pub struct Costs {
+    table: Vec<Blueprint>,
+}
+
+impl Costs {
+    /// The most geodes each blueprint can open in `minutes`.
+    pub fn geodes(&self, minutes: usize, blueprints: usize) -> Vec<usize> {
+        self.table
+            .iter()
+            .take(blueprints)
+            .map(|blueprint| BlueprintStateTable::after(blueprint, minutes).geodes())
+            .collect()
+    }
+
+    pub fn part_1_score(&self, minutes: usize) -> usize {
+        self.geodes(minutes, self.table.len())
+            .iter()
+            .enumerate()
+            .map(|(blueprint, geodes)| (blueprint + 1) * geodes)
+            .sum()
+    }
+
+    pub fn part_2_score(&self, minutes: usize) -> usize {
+        self.geodes(minutes, 3).iter().product()
+    }
+
+    pub fn construct(
+        &self,
+        blueprint: usize,
+        robot: Mineral,
+        mined_minerals: &EnumMap<Mineral, usize>,
+    ) -> Option<EnumMap<Mineral, usize>> {
+        let mut result = mined_minerals.clone();
+        for (mineral, cost) in self.table[blueprint][robot].iter() {
+            if *cost > result[mineral] {
+                return None;
+            } else {
+                result[mineral] -= cost;
+            }
+        }
+        Some(result)
+    }
+
+    pub fn from_file(filename: &str) -> anyhow::Result<Self> {
+        Self::from_input(&Input::from_file(filename)?)
+    }
+
+    pub fn from_input(input: &Input) -> anyhow::Result<Self> {
+        let table = parse_lines(input, |line| {
+            let mut fields = Fields::new(line);
+            fields.expect("Blueprint")?;
+            fields.parse_between::<usize>("", ":", "a blueprint number")?;
+            let mut this_table = EnumMap::default();
+            let costs = [
+                robot_costs(&mut fields, "ore", &[Mineral::Ore])?,
+                robot_costs(&mut fields, "clay", &[Mineral::Ore])?,
+                robot_costs(&mut fields, "obsidian", &[Mineral::Ore, Mineral::Clay])?,
+                robot_costs(&mut fields, "geode", &[Mineral::Ore, Mineral::Obsidian])?,
+            ];
+            fields.end()?;
+            for (mineral, cost) in zip(all::<Mineral>(), costs.iter()) {
+                this_table[mineral] = cost.iter().copied().collect();
+            }
+            Ok(this_table)
+        })?;
+        Ok(Self { table })
+    }
+}
+
+fn robot_costs(
+    fields: &mut Fields,
+    robot: &str,
+    minerals: &[Mineral],
+) -> LineResult<Vec<(Mineral, usize)>> {
+    for word in ["Each", robot, "robot", "costs"] {
+        fields.expect(word)?;
+    }
+    let mut costs = vec![];
+    for (i, mineral) in minerals.iter().enumerate() {
+        if i > 0 {
+            fields.expect("and")?;
+        }
+        costs.push((*mineral, fields.parse("a cost")?));
+        let name = mineral.name();
+        if i + 1 == minerals.len() {
+            fields.expect(format!("{name}.").as_str())?;
+        } else {
+            fields.expect(name)?;
+        }
+    }
+    Ok(costs)
+}
+
+#[derive(Copy, Clone, Hash, PartialEq, Eq, PartialOrd, Ord, Debug, Enum, Sequence)]
+pub enum Mineral {
+    Ore,
+    Clay,
+    Obsidian,
+    Geode,
+}
+
+impl Mineral {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Ore => "ore",
+            Self::Clay => "clay",
+            Self::Obsidian => "obsidian",
+            Self::Geode => "geode",
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct BlueprintStateTable {
+    states: Vec<HashSet<State>>,
+}
+
+impl BlueprintStateTable {
+    pub fn after(blueprint: &Blueprint, minutes: usize) -> Self {
+        let mut states: Vec<HashSet<State>> = vec![[State::default()].iter().cloned().collect()];
+        for minute in 1..=minutes {
+            let mut new_states = HashSet::new();
+            let mut most_geodes_produced = 0;
+            for state in states[minute - 1].iter() {
+                for successor in state.successors(blueprint) {
+                    let bound = successor.geode_production_upper_bound(minutes - minute, blueprint);
+                    if bound > most_geodes_produced {
+                        most_geodes_produced = max(successor.geodes_mined(), most_geodes_produced);
+                        new_states.insert(successor);
+                    }
+                }
+            }
+            states.push(new_states);
+        }
+        Self { states }
+    }
+
+    pub fn geodes(&self) -> usize {
+        self.states
+            .last()
+            .unwrap()
+            .iter()
+            .map(|s| s.mined_minerals[Mineral::Geode])
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct State {
+    robot_count: EnumMap<Mineral, usize>,
+    mined_minerals: EnumMap<Mineral, usize>,
+}
+
+impl Default for State {
+    fn default() -> Self {
+        let mut robot_count = EnumMap::default();
+        robot_count[Mineral::Ore] = 1;
+        State {
+            robot_count,
+            mined_minerals: EnumMap::default(),
+        }
+    }
+}
+
+impl State {
+    pub fn successors(&self, blueprint: &Blueprint) -> Vec<Self> {
+        let mut result = vec![];
+        for robot in reverse_all::<Mineral>() {
+            if let Some(after_use) = build_robot(blueprint, robot, &self.mined_minerals) {
+                let mut successor = Self {
+                    robot_count: self.robot_count.clone(),
+                    mined_minerals: after_use,
+                };
+                successor.mine();
+                successor.robot_count[robot] += 1;
+                match robot {
+                    Mineral::Geode => {
+                        return vec![successor];
+                    }
+                    _ => {
+                        result.push(successor);
+                    }
+                }
+            }
+        }
+        let mut no_build = self.clone();
+        no_build.mine();
+        result.push(no_build);
+        result
+    }
+
+    pub fn mine(&mut self) {
+        for robot in all::<Mineral>() {
+            self.mined_minerals[robot] += self.robot_count[robot];
+        }
+    }
+
+    pub fn geodes_mined(&self) -> usize {
+        self.mined_minerals[Mineral::Geode]
+    }
+
+    pub fn production_upper_bound_for(
+        &self,
+        max_extra_robots: usize,
+        mineral: Mineral,
+        minutes_left: usize,
+    ) -> usize {
+        let mut current_mineral = self.mined_minerals[mineral];
+        let mut current_robots = self.robot_count[mineral];
+        for _ in 0..minutes_left {
+            current_mineral += current_robots;
+            if current_robots < self.robot_count[mineral] + max_extra_robots {
+                current_robots += 1;
+            }
+        }
+        current_mineral
+    }
+
+    pub fn geode_production_upper_bound(
+        &self,
+        minutes_left: usize,
+        blueprint: &Blueprint,
+    ) -> usize {
+        let ore_upper_bound = self.production_upper_bound_for(0, Mineral::Ore, minutes_left);
+        let max_clay_robots = ore_upper_bound / blueprint[Mineral::Clay][Mineral::Ore];
+        let clay_upper_bound =
+            self.production_upper_bound_for(max_clay_robots, Mineral::Clay, minutes_left);
+        let max_obsidian_robots = clay_upper_bound / blueprint[Mineral::Obsidian][Mineral::Clay];
+        let obsidian_upper_bound =
+            self.production_upper_bound_for(max_obsidian_robots, Mineral::Obsidian, minutes_left);
+        let max_geode_robots = obsidian_upper_bound / blueprint[Mineral::Geode][Mineral::Obsidian];
+        self.production_upper_bound_for(max_geode_robots, Mineral::Geode, minutes_left)
+    }
+}
+
+impl Solution for Costs {
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn load(input: &Input) -> anyhow::Result<Self> {
+        Self::from_input(input)
+    }
+
+    fn part1(&self) -> anyhow::Result<usize> {
+        Ok(part1(self))
+    }
+
+    fn part2(&self) -> anyhow::Result<usize> {
+        Ok(part2(self))
+    }
+
+    fn diagnostics(&self) -> Vec<(String, String)> {
+        self.geodes(24, self.table.len())
+            .iter()
+            .enumerate()
+            .map(|(blueprint, geodes)| {
+                (
+                    format!("blueprint {} geodes", blueprint + 1),
+                    geodes.to_string(),
+                )
+            })
+            .collect()
+    }
+}
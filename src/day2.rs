@@ -0,0 +1,595 @@
+use anyhow::{anyhow, bail};
+use enum_iterator::*;
+use enum_map::{Enum, EnumMap};
+
+use crate::{
+    input::Input,
+    parse::{parse_lines, Fields, LineError},
+    Solution,
+};
+
+#[derive(Clone, Debug)]
+pub struct StrategyGuide {
+    rules: Rules,
+    rounds: Vec<(usize, String)>,
+}
+
+impl StrategyGuide {
+    pub fn from_file(filename: &str) -> anyhow::Result<Self> {
+        Self::from_input(&Input::from_file(filename)?)
+    }
+
+    pub fn from_input(input: &Input) -> anyhow::Result<Self> {
+        Self::with_rules(input, Rules::default())
+    }
+
+    pub fn with_rules(input: &Input, rules: Rules) -> anyhow::Result<Self> {
+        let opponent_codes = or_list(rules.shapes.iter().map(|s| s.opponent_code.as_str()));
+        let mut codes = rules
+            .shapes
+            .iter()
+            .map(|s| s.code.as_str())
+            .chain(rules.outcomes.values().map(|(_, code)| code.as_str()))
+            .collect::<Vec<_>>();
+        codes.sort();
+        codes.dedup();
+        let codes = or_list(codes.into_iter());
+        let rounds = parse_lines(input, |line| {
+            let mut fields = Fields::new(line);
+            let (column, opponent) = fields.next(opponent_codes.as_str())?;
+            let opponent = rules
+                .opponent_shape(opponent)
+                .ok_or_else(|| LineError::new(column, opponent_codes.as_str(), opponent))?;
+            let (column, code) = fields.next(codes.as_str())?;
+            if rules.my_shape(code).is_none() && rules.outcome(code).is_none() {
+                return Err(LineError::new(column, codes.as_str(), code));
+            }
+            fields.end()?;
+            Ok((opponent, code.to_owned()))
+        })?;
+        Ok(Self { rules, rounds })
+    }
+
+    /// How often the opponent plays each shape.
+    pub fn opponent_frequencies(&self) -> Vec<f64> {
+        let mut counts = vec![0.0; self.rules.shapes.len()];
+        for (opponent, _) in self.rounds.iter() {
+            counts[*opponent] += 1.0;
+        }
+        counts
+            .iter()
+            .map(|c| c / self.rounds.len().max(1) as f64)
+            .collect()
+    }
+
+    pub fn analysis(&self) -> anyhow::Result<Analysis> {
+        let frequencies = self.opponent_frequencies();
+        let payoff = self.rules.payoff_matrix();
+        let expected = |strategy: &[f64]| -> f64 {
+            (0..strategy.len())
+                .map(|me| {
+                    let row = (0..frequencies.len()).map(|o| payoff[me][o] * frequencies[o]);
+                    strategy[me] * row.sum::<f64>()
+                })
+                .sum()
+        };
+        let pure = |me: usize| -> Vec<f64> {
+            (0..payoff.len())
+                .map(|i| if i == me { 1.0 } else { 0.0 })
+                .collect()
+        };
+        let best_response = (0..payoff.len())
+            .max_by(|a, b| expected(&pure(*a)).total_cmp(&expected(&pure(*b))))
+            .unwrap();
+        let best_response_score = expected(&pure(best_response));
+        let (mixed, value) = maximin(&payoff);
+        let mixed_score = expected(&mixed);
+        let rounds = self.rounds.len().max(1) as f64;
+        Ok(Analysis {
+            frequencies,
+            best_response,
+            best_response_score,
+            mixed,
+            mixed_score,
+            value,
+            guide1_score: self.part1()? as f64 / rounds,
+            guide2_score: self.part2()? as f64 / rounds,
+        })
+    }
+}
+
+/// Expected scores per round for different ways of choosing our moves.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Analysis {
+    /// How often the opponent played each shape.
+    pub frequencies: Vec<f64>,
+    /// The single shape that scores best against those frequencies.
+    pub best_response: usize,
+    pub best_response_score: f64,
+    /// How often to play each shape to score best against the worst opponent.
+    pub mixed: Vec<f64>,
+    pub mixed_score: f64,
+    /// The score `mixed` guarantees whatever the opponent does.
+    pub value: f64,
+    /// The guide read as moves, as in part 1.
+    pub guide1_score: f64,
+    /// The guide read as outcomes, as in part 2.
+    pub guide2_score: f64,
+}
+
+impl Analysis {
+    pub fn report(&self, rules: &Rules) -> String {
+        let percentages = |strategy: &[f64]| {
+            rules
+                .shapes
+                .iter()
+                .zip(strategy.iter())
+                .map(|(shape, p)| format!("{} {:.1}%", shape.name, p * 100.0))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        let best = rules.shapes[self.best_response].name.as_str();
+        [
+            format!("Opponent plays: {}", percentages(&self.frequencies)),
+            format!(
+                "Best response: always {best}, {:.3} per round",
+                self.best_response_score
+            ),
+            format!("Optimal mix: {}", percentages(&self.mixed)),
+            format!(
+                "  {:.3} per round here, at least {:.3} against anyone",
+                self.mixed_score, self.value
+            ),
+            format!("Guide as moves: {:.3} per round", self.guide1_score),
+            format!("Guide as outcomes: {:.3} per round", self.guide2_score),
+        ]
+        .iter()
+        .map(|line| format!("{line}\n"))
+        .collect()
+    }
+}
+
+/// The mixed strategy for the row player that maximises their worst-case
+/// expected payoff, and that payoff. Solves the column player's linear
+/// program with the simplex method; the row player's strategy comes from
+/// its dual values.
+pub fn maximin(payoff: &[Vec<f64>]) -> (Vec<f64>, f64) {
+    let rows = payoff.len();
+    let cols = payoff[0].len();
+    let lowest = payoff
+        .iter()
+        .flatten()
+        .copied()
+        .fold(f64::INFINITY, f64::min);
+    let shift = 1.0 - lowest;
+
+    // Maximise the sum of z subject to (payoff + shift) z <= 1, with a
+    // slack variable for each row.
+    let width = cols + rows + 1;
+    let mut tableau = vec![vec![0.0; width]; rows + 1];
+    for (r, row) in payoff.iter().enumerate() {
+        for (c, p) in row.iter().enumerate() {
+            tableau[r][c] = p + shift;
+        }
+        tableau[r][cols + r] = 1.0;
+        tableau[r][width - 1] = 1.0;
+    }
+    for value in tableau[rows].iter_mut().take(cols) {
+        *value = -1.0;
+    }
+    let mut basis = (cols..cols + rows).collect::<Vec<_>>();
+
+    const EPSILON: f64 = 1e-12;
+    while let Some(entering) = (0..width - 1).find(|c| tableau[rows][*c] < -EPSILON) {
+        let leaving = (0..rows)
+            .filter(|r| tableau[*r][entering] > EPSILON)
+            .min_by(|a, b| {
+                let ratio = |r: usize| tableau[r][width - 1] / tableau[r][entering];
+                ratio(*a)
+                    .total_cmp(&ratio(*b))
+                    .then(basis[*a].cmp(&basis[*b]))
+            })
+            .unwrap();
+        let pivot = tableau[leaving][entering];
+        for value in tableau[leaving].iter_mut() {
+            *value /= pivot;
+        }
+        let pivot_row = tableau[leaving].clone();
+        for (r, row) in tableau.iter_mut().enumerate() {
+            if r != leaving {
+                let factor = row[entering];
+                for (value, p) in row.iter_mut().zip(pivot_row.iter()) {
+                    *value -= factor * p;
+                }
+            }
+        }
+        basis[leaving] = entering;
+    }
+
+    let total = tableau[rows][width - 1];
+    let strategy = (0..rows).map(|r| tableau[rows][cols + r] / total).collect();
+    (strategy, 1.0 / total - shift)
+}
+
+impl Solution for StrategyGuide {
+    type Part1 = i64;
+    type Part2 = i64;
+
+    fn load(input: &Input) -> anyhow::Result<Self> {
+        Self::from_input(input)
+    }
+
+    fn part1(&self) -> anyhow::Result<i64> {
+        self.rounds
+            .iter()
+            .map(|(opponent, code)| {
+                self.rules
+                    .my_shape(code.as_str())
+                    .map(|me| self.rules.match_score(me, *opponent))
+                    .ok_or_else(|| anyhow!("{code}: Not a shape"))
+            })
+            .sum()
+    }
+
+    fn part2(&self) -> anyhow::Result<i64> {
+        self.rounds
+            .iter()
+            .map(|(opponent, code)| {
+                self.rules
+                    .strategy2(*opponent, code.as_str())
+                    .map(|me| self.rules.match_score(me, *opponent))
+                    .ok_or_else(|| anyhow!("{code}: Not a strategy"))
+            })
+            .sum()
+    }
+
+    fn diagnostics(&self) -> Vec<(String, String)> {
+        match self.analysis() {
+            Ok(analysis) => vec![
+                (
+                    "best response".to_owned(),
+                    self.rules.shapes[analysis.best_response].name.clone(),
+                ),
+                (
+                    "best response per round".to_owned(),
+                    format!("{:.3}", analysis.best_response_score),
+                ),
+                (
+                    "optimal mix per round".to_owned(),
+                    format!("{:.3}", analysis.mixed_score),
+                ),
+            ],
+            Err(_) => vec![],
+        }
+    }
+}
+
+/// Joins codes as in "A, B or C".
+fn or_list<'a, I: Iterator<Item = &'a str>>(codes: I) -> String {
+    let codes = codes.collect::<Vec<_>>();
+    match codes.split_last() {
+        Some((last, [])) => last.to_string(),
+        Some((last, rest)) => format!("{} or {last}", rest.join(", ")),
+        None => String::new(),
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Sequence, Enum, Debug)]
+pub enum Outcome {
+    Lose,
+    Draw,
+    Win,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Shape {
+    pub name: String,
+    pub score: i64,
+    /// Stands for this shape in the opponent's column.
+    pub opponent_code: String,
+    /// Stands for this shape in our column, when read as a move.
+    pub code: String,
+}
+
+enum RuleLine {
+    Shape(Shape),
+    /// An outcome's score and code, with the code's column.
+    Outcome(Outcome, i64, String, usize),
+}
+
+/// A cyclic game: each shape defeats the shapes an odd number of places
+/// before it in the cycle, and loses to the rest.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Rules {
+    shapes: Vec<Shape>,
+    /// The score for each outcome, and the code that asks for it.
+    outcomes: EnumMap<Outcome, (i64, String)>,
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        let shape = |name: &str, score, opponent_code: &str, code: &str| Shape {
+            name: name.to_owned(),
+            score,
+            opponent_code: opponent_code.to_owned(),
+            code: code.to_owned(),
+        };
+        Self {
+            shapes: vec![
+                shape("Rock", 1, "A", "X"),
+                shape("Paper", 2, "B", "Y"),
+                shape("Scissors", 3, "C", "Z"),
+            ],
+            outcomes: EnumMap::from_array([
+                (0, "X".to_owned()),
+                (3, "Y".to_owned()),
+                (6, "Z".to_owned()),
+            ]),
+        }
+    }
+}
+
+impl Rules {
+    pub fn from_file(filename: &str) -> anyhow::Result<Self> {
+        Self::from_input(&Input::from_file(filename)?)
+    }
+
+    /// Reads lines such as `shape Rock 1 A X` (name, score, opponent's code,
+    /// our code) in cycle order, and `win 6 Z` (score and code for an
+    /// outcome). Outcomes not given keep their usual score and code.
+    pub fn from_input(input: &Input) -> anyhow::Result<Self> {
+        let mut rules = Self {
+            shapes: vec![],
+            ..Self::default()
+        };
+        let lines = parse_lines(input, |line| {
+            let mut fields = Fields::new(line);
+            if fields.end().is_ok() || line.trim_start().starts_with('#') {
+                return Ok(None);
+            }
+            let (column, kind) = fields.next("shape, win, draw or lose")?;
+            let line = match kind {
+                "shape" => {
+                    let name = fields.next("a name")?.1.to_owned();
+                    let score = fields.parse("a score")?;
+                    let opponent_code = fields.next("the opponent's code")?.1.to_owned();
+                    let code = fields.next("our code")?.1.to_owned();
+                    Ok(RuleLine::Shape(Shape {
+                        name,
+                        score,
+                        opponent_code,
+                        code,
+                    }))
+                }
+                "win" | "draw" | "lose" => {
+                    let outcome = match kind {
+                        "win" => Outcome::Win,
+                        "draw" => Outcome::Draw,
+                        _ => Outcome::Lose,
+                    };
+                    let score = fields.parse("a score")?;
+                    let (column, code) = fields.next("a code")?;
+                    Ok(RuleLine::Outcome(outcome, score, code.to_owned(), column))
+                }
+                _ => Err(LineError::new(column, "shape, win, draw or lose", kind)),
+            }?;
+            fields.end()?;
+            Ok(Some(line))
+        })?;
+        let mut given = EnumMap::<Outcome, Option<(usize, usize)>>::default();
+        for (i, line) in lines.into_iter().enumerate() {
+            match line {
+                Some(RuleLine::Outcome(outcome, score, code, column)) => {
+                    rules.outcomes[outcome] = (score, code);
+                    given[outcome] = Some((i + 1, column));
+                }
+                Some(RuleLine::Shape(shape)) => rules.shapes.push(shape),
+                None => {}
+            }
+        }
+        // Outcomes left as they were come first, then the rest in file order,
+        // so a clash is reported where the second code was given.
+        let mut outcomes = all::<Outcome>().collect::<Vec<_>>();
+        outcomes.sort_by_key(|outcome| given[*outcome]);
+        for (i, outcome) in outcomes.iter().enumerate() {
+            let code = rules.outcomes[*outcome].1.as_str();
+            let clash = outcomes[..i]
+                .iter()
+                .any(|earlier| rules.outcomes[*earlier].1 == code);
+            if let (true, Some((line, column))) = (clash, given[*outcome]) {
+                return Err(LineError::new(column, "a code no other outcome uses", code)
+                    .at(input.name(), line)
+                    .into());
+            }
+        }
+
+        let count = rules.shapes.len();
+        if count < 3 || count.is_multiple_of(2) {
+            bail!(
+                "{}: {count} shapes, but a fair cycle needs an odd number of at least 3",
+                input.name()
+            );
+        }
+        for (i, shape) in rules.shapes.iter().enumerate() {
+            let earlier = &rules.shapes[..i];
+            if earlier
+                .iter()
+                .any(|s| s.opponent_code == shape.opponent_code)
+            {
+                bail!(
+                    "{}: Opponent code {} is used twice",
+                    input.name(),
+                    shape.opponent_code
+                );
+            }
+            if earlier.iter().any(|s| s.code == shape.code) {
+                bail!("{}: Code {} is used twice", input.name(), shape.code);
+            }
+        }
+        Ok(rules)
+    }
+
+    pub fn shapes(&self) -> &[Shape] {
+        &self.shapes
+    }
+
+    pub fn opponent_shape(&self, code: &str) -> Option<usize> {
+        self.shapes.iter().position(|s| s.opponent_code == code)
+    }
+
+    pub fn my_shape(&self, code: &str) -> Option<usize> {
+        self.shapes.iter().position(|s| s.code == code)
+    }
+
+    pub fn outcome(&self, code: &str) -> Option<Outcome> {
+        all::<Outcome>().find(|o| self.outcomes[*o].1 == code)
+    }
+
+    pub fn defeats(&self, me: usize, opponent: usize) -> bool {
+        let n = self.shapes.len();
+        (me + n - opponent) % n % 2 == 1
+    }
+
+    pub fn outcome_of(&self, me: usize, opponent: usize) -> Outcome {
+        if self.defeats(me, opponent) {
+            Outcome::Win
+        } else if self.defeats(opponent, me) {
+            Outcome::Lose
+        } else {
+            Outcome::Draw
+        }
+    }
+
+    /// The shape that gets `outcome` against `opponent`.
+    pub fn move2match(&self, opponent: usize, outcome: Outcome) -> usize {
+        (0..self.shapes.len())
+            .find(|me| self.outcome_of(*me, opponent) == outcome)
+            .unwrap()
+    }
+
+    pub fn strategy2(&self, opponent: usize, strategy: &str) -> Option<usize> {
+        self.outcome(strategy)
+            .map(|outcome| self.move2match(opponent, outcome))
+    }
+
+    pub fn shape_score(&self, me: usize) -> i64 {
+        self.shapes[me].score
+    }
+
+    pub fn game_score(&self, me: usize, opponent: usize) -> i64 {
+        self.outcomes[self.outcome_of(me, opponent)].0
+    }
+
+    pub fn match_score(&self, me: usize, opponent: usize) -> i64 {
+        self.game_score(me, opponent) + self.shape_score(me)
+    }
+
+    /// Our score for each of our shapes (rows) against each of the
+    /// opponent's (columns).
+    pub fn payoff_matrix(&self) -> Vec<Vec<f64>> {
+        let n = self.shapes.len();
+        (0..n)
+            .map(|me| {
+                (0..n)
+                    .map(|opponent| self.match_score(me, opponent) as f64)
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        day2::{maximin, Outcome, Rules, StrategyGuide},
+        input::Input,
+        Solution,
+    };
+
+    const SPOCK: &str = "# Rock, paper, scissors, Spock, lizard
+shape Rock 1 A V
+shape Paper 2 B W
+shape Scissors 3 C X
+shape Spock 4 D Y
+shape Lizard 5 E Z
+win 10 Z
+draw 5 Y
+lose 0 X
+";
+
+    #[test]
+    fn test_rules() {
+        let rules = Rules::from_input(&Input::from(SPOCK)).unwrap();
+        let [rock, paper, scissors, spock, lizard] = [0, 1, 2, 3, 4];
+        for (winner, loser) in [
+            (scissors, paper),
+            (paper, rock),
+            (rock, lizard),
+            (lizard, spock),
+            (spock, scissors),
+            (scissors, lizard),
+            (lizard, paper),
+            (paper, spock),
+            (spock, rock),
+            (rock, scissors),
+        ] {
+            assert!(rules.defeats(winner, loser));
+            assert_eq!(rules.outcome_of(loser, winner), Outcome::Lose);
+        }
+        assert_eq!(rules.match_score(lizard, lizard), 10);
+
+        let guide = StrategyGuide::with_rules(&Input::from("A Z\nD Y\nE X"), rules).unwrap();
+        assert_eq!(guide.part1().unwrap(), 5 + 9 + 13);
+        assert_eq!(guide.part2().unwrap(), 12 + 9 + 2);
+
+        let even = SPOCK.replace("shape Lizard 5 E Z\n", "");
+        let error = Rules::from_input(&Input::from(even.as_str())).unwrap_err();
+        assert!(error.to_string().contains("4 shapes"));
+
+        let clash = format!("{SPOCK}lose 0 Z\n");
+        let error = Rules::from_input(&Input::from(clash.as_str())).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "<string>:10:8: expected a code no other outcome uses, found \"Z\""
+        );
+        let swapped =
+            "shape Rock 1 A X\nshape Paper 2 B Y\nshape Scissors 3 C Z\nwin 6 X\nlose 0 Z\n";
+        assert!(Rules::from_input(&Input::from(swapped)).is_ok());
+    }
+
+    #[test]
+    fn test_codes() {
+        let error = StrategyGuide::from_input(&Input::from("A Y\nD X")).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "<string>:2:1: expected A, B or C, found \"D\""
+        );
+    }
+
+    #[test]
+    fn test_maximin() {
+        let matching_pennies = vec![vec![1.0, -1.0], vec![-1.0, 1.0]];
+        let (strategy, value) = maximin(&matching_pennies);
+        assert!((strategy[0] - 0.5).abs() < 1e-9 && value.abs() < 1e-9);
+
+        let payoff = Rules::default().payoff_matrix();
+        let (strategy, value) = maximin(&payoff);
+        assert!((strategy.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+        for opponent in 0..3 {
+            let score = (0..3).map(|me| strategy[me] * payoff[me][opponent]);
+            assert!(score.sum::<f64>() >= value - 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_analysis() {
+        let guide = StrategyGuide::from_file("ex/day2.txt").unwrap();
+        let analysis = guide.analysis().unwrap();
+        assert_eq!(analysis.frequencies, vec![1.0 / 3.0; 3]);
+        assert_eq!(analysis.guide1_score, 5.0);
+        assert_eq!(analysis.guide2_score, 4.0);
+        assert_eq!(analysis.best_response, 2);
+        assert_eq!(analysis.best_response_score, 6.0);
+        assert!(analysis.best_response_score >= analysis.mixed_score - 1e-9);
+    }
+}
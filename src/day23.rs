@@ -0,0 +1,220 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Display,
+};
+
+use advent_code_lib::{Dir, ManhattanDir, Point};
+use bare_metal_modulo::{MNum, ModNumC};
+use enum_iterator::all;
+
+use crate::{
+    input::Input,
+    parse::{parse_lines, LineError},
+    simulation::Simulation,
+    Solution,
+};
+
+type Elf = Point<isize, 2>;
+
+const ORDERING: [ManhattanDir; 4] = [
+    ManhattanDir::N,
+    ManhattanDir::S,
+    ManhattanDir::W,
+    ManhattanDir::E,
+];
+const ORDERING_LEN: usize = ORDERING.len();
+
+pub fn part1(elves: &CellularElves) -> usize {
+    let mut elves = elves.clone();
+    for _ in 0..10 {
+        elves.round();
+    }
+    elves.empty_space()
+}
+
+pub fn part2(elves: &CellularElves) -> usize {
+    let mut elves = elves.clone();
+    let mut rounds = 0;
+    loop {
+        let prev = elves.elves.clone();
+        elves.round();
+        rounds += 1;
+        if prev == elves.elves {
+            return rounds;
+        }
+    }
+}
+
+impl Solution for CellularElves {
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn load(input: &Input) -> anyhow::Result<Self> {
+        Self::from_input(input)
+    }
+
+    fn part1(&self) -> anyhow::Result<usize> {
+        Ok(part1(self))
+    }
+
+    fn part2(&self) -> anyhow::Result<usize> {
+        Ok(part2(self))
+    }
+
+    fn drawing(&self) -> Option<String> {
+        let mut elves = self.clone();
+        for _ in 0..10 {
+            elves.round();
+        }
+        Some(elves.to_string())
+    }
+
+    fn simulation(&self) -> Option<Box<dyn Simulation>> {
+        Some(Box::new(self.clone()))
+    }
+}
+
+impl Simulation for CellularElves {
+    /// Runs one round, until no elf moves.
+    fn step(&mut self) -> bool {
+        let prev = self.elves.clone();
+        self.round();
+        prev != self.elves
+    }
+
+    fn snapshot(&self) -> String {
+        self.to_string()
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct CellularElves {
+    elves: BTreeSet<Elf>,
+    dir_start: ModNumC<usize, ORDERING_LEN>,
+}
+
+impl CellularElves {
+    pub fn from_file(filename: &str) -> anyhow::Result<Self> {
+        Self::from_input(&Input::from_file(filename)?)
+    }
+
+    pub fn from_input(input: &Input) -> anyhow::Result<Self> {
+        let mut elves = BTreeSet::new();
+        let mut row = 0;
+        parse_lines(input, |line| {
+            for (col, c) in line.chars().enumerate() {
+                match c {
+                    '#' => {
+                        elves.insert(Elf::new([col as isize, row]));
+                    }
+                    '.' => {}
+                    _ => return Err(LineError::new(col + 1, "# or .", c.to_string().as_str())),
+                }
+            }
+            row += 1;
+            Ok(())
+        })?;
+        Ok(Self {
+            elves,
+            dir_start: ModNumC::new(0),
+        })
+    }
+
+    pub fn elf(&self, col: isize, row: isize) -> bool {
+        self.elves.contains(&Elf::new([col, row]))
+    }
+
+    pub fn min_elf_rectangle_pts(&self) -> Vec<(isize, isize)> {
+        let mut result = vec![];
+        let (min_elf, max_elf) = Elf::min_max_points(self.elves.iter().copied()).unwrap();
+        for row in min_elf[1]..=max_elf[1] {
+            for col in min_elf[0]..=max_elf[0] {
+                result.push((col, row));
+            }
+        }
+        result
+    }
+
+    pub fn round(&mut self) {
+        let starting_elves = self.elves.len();
+        let proposals = self.proposed_moves();
+        for (end, start) in proposals
+            .iter()
+            .filter(|(_, ps)| ps.len() == 1)
+            .map(|(end, start)| (*end, start[0]))
+        {
+            self.elves.remove(&start);
+            self.elves.insert(end);
+        }
+        assert_eq!(starting_elves, self.elves.len());
+        self.dir_start += 1;
+    }
+
+    pub fn proposed_moves(&self) -> BTreeMap<Elf, Vec<Elf>> {
+        let mut end2starts = BTreeMap::new();
+        for elf in self.elves.iter() {
+            if let Some(proposal) = self.proposed_move(*elf) {
+                match end2starts.get_mut(&proposal) {
+                    None => {
+                        end2starts.insert(proposal, vec![*elf]);
+                    }
+                    Some(v) => {
+                        v.push(*elf);
+                    }
+                }
+            }
+        }
+        end2starts
+    }
+
+    fn proposed_move(&self, elf: Elf) -> Option<Elf> {
+        if all::<Dir>().all(|n| !self.elves.contains(&elf.dir_moved(n))) {
+            None
+        } else {
+            self.dir_start
+                .iter()
+                .find_map(|i| self.proposal_for(elf, ORDERING[i.a()]))
+        }
+    }
+
+    fn proposal_for(&self, elf: Elf, dir: ManhattanDir) -> Option<Elf> {
+        if dir_check(dir)
+            .iter()
+            .all(|check| !self.elves.contains(&elf.dir_moved(*check)))
+        {
+            Some(elf.manhattan_moved(dir))
+        } else {
+            None
+        }
+    }
+
+    pub fn empty_space(&self) -> usize {
+        self.min_elf_rectangle_pts()
+            .iter()
+            .filter(|(col, row)| !self.elf(*col, *row))
+            .count()
+    }
+}
+
+fn dir_check(dir: ManhattanDir) -> [Dir; 3] {
+    match dir {
+        ManhattanDir::N => [Dir::Ne, Dir::N, Dir::Nw],
+        ManhattanDir::E => [Dir::Ne, Dir::E, Dir::Se],
+        ManhattanDir::S => [Dir::Se, Dir::S, Dir::Sw],
+        ManhattanDir::W => [Dir::Nw, Dir::W, Dir::Sw],
+    }
+}
+
+impl Display for CellularElves {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (min_elf, max_elf) = Elf::min_max_points(self.elves.iter().copied()).unwrap();
+        for row in min_elf[1]..=max_elf[1] {
+            for col in min_elf[0]..=max_elf[0] {
+                let c = if self.elf(col, row) { '#' } else { '.' };
+                write!(f, "{c}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,406 @@
+use std::{
+    cmp::max,
+    collections::{BTreeMap, BTreeSet},
+    fmt::Display,
+};
+
+use advent_code_lib::{ManhattanDir, Point};
+use enum_iterator::all;
+
+use crate::{
+    cycle::{CycleDetector, Cyclic},
+    input::Input,
+    parse::{parse_lines, LineError},
+    simulation::Simulation,
+    Solution,
+};
+
+type Pt = Point<isize, 2>;
+
+pub fn part1(map: &BlizzardMap) -> usize {
+    let mut map = map.clone();
+    let start = map.entrance();
+    let exit = map.exit();
+    Reachability::minutes2exit(&mut map, start, exit)
+}
+
+pub fn part2(map: &BlizzardMap) -> usize {
+    let mut map = map.clone();
+    let start = map.entrance();
+    let exit = map.exit();
+    let mut total = Reachability::minutes2exit(&mut map, start, exit);
+    total += Reachability::minutes2exit(&mut map, exit, start);
+    total += Reachability::minutes2exit(&mut map, start, exit);
+    total
+}
+
+impl Solution for BlizzardMap {
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn load(input: &Input) -> anyhow::Result<Self> {
+        Self::from_input(input)
+    }
+
+    fn part1(&self) -> anyhow::Result<usize> {
+        Ok(part1(self))
+    }
+
+    fn part2(&self) -> anyhow::Result<usize> {
+        Ok(part2(self))
+    }
+
+    fn diagnostics(&self) -> Vec<(String, String)> {
+        match self.period() {
+            Ok(period) => vec![("blizzard period".to_owned(), format!("{period}"))],
+            Err(_) => vec![],
+        }
+    }
+
+    fn drawing(&self) -> Option<String> {
+        Some(self.to_string())
+    }
+
+    fn simulation(&self) -> Option<Box<dyn Simulation>> {
+        Some(Box::new(Expedition::new(self)))
+    }
+}
+
+impl Simulation for BlizzardMap {
+    /// Moves every blizzard one minute on; they never stop.
+    fn step(&mut self) -> bool {
+        *self = self.next_step();
+        true
+    }
+
+    fn snapshot(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl Cyclic for BlizzardMap {
+    type Fingerprint = String;
+
+    fn advance(&mut self) {
+        *self = self.next_step();
+    }
+
+    fn fingerprint(&self) -> String {
+        self.to_string()
+    }
+}
+
+/// The first trip to the exit, as every position the expedition could be in
+/// after each minute.
+pub struct Expedition {
+    map: BlizzardMap,
+    reachability: Reachability,
+    exit: Pt,
+}
+
+impl Expedition {
+    pub fn new(map: &BlizzardMap) -> Self {
+        Self {
+            map: map.clone(),
+            reachability: Reachability::new(map.entrance()),
+            exit: map.exit(),
+        }
+    }
+}
+
+impl Simulation for Expedition {
+    /// Moves on a minute, until the exit is reachable.
+    fn step(&mut self) -> bool {
+        if self.reachability.current().contains(&self.exit) {
+            false
+        } else {
+            self.reachability.iterate(&mut self.map);
+            true
+        }
+    }
+
+    /// Draws the map with E wherever the expedition might be.
+    fn snapshot(&self) -> String {
+        let mut rows = self
+            .map
+            .to_string()
+            .lines()
+            .map(|line| line.chars().collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        for p in self.reachability.current().iter() {
+            rows[p[1] as usize][p[0] as usize] = 'E';
+        }
+        rows.iter()
+            .map(|row| row.iter().collect::<String>() + "\n")
+            .collect()
+    }
+}
+
+struct Reachability {
+    minute2reachable: Vec<BTreeSet<Pt>>,
+}
+
+impl Reachability {
+    fn new(start: Pt) -> Self {
+        let mut minute_zero = BTreeSet::new();
+        minute_zero.insert(start);
+        Self {
+            minute2reachable: vec![minute_zero],
+        }
+    }
+
+    fn current(&self) -> &BTreeSet<Pt> {
+        self.minute2reachable.last().unwrap()
+    }
+
+    fn elapsed_minutes(&self) -> usize {
+        self.minute2reachable.len() - 1
+    }
+
+    fn iterate(&mut self, map: &mut BlizzardMap) {
+        *map = map.next_step();
+        let mut reachable = BTreeSet::new();
+        for prev in self.current().iter() {
+            if map.can_enter(*prev) {
+                reachable.insert(*prev);
+            }
+            for neighbor in prev.manhattan_neighbors() {
+                if map.can_enter(neighbor) {
+                    reachable.insert(neighbor);
+                }
+            }
+        }
+        self.minute2reachable.push(reachable);
+    }
+
+    fn minutes2exit(map: &mut BlizzardMap, start: Pt, exit: Pt) -> usize {
+        let mut reachability = Self::new(start);
+        while !reachability.current().contains(&exit) {
+            reachability.iterate(map);
+        }
+        reachability.elapsed_minutes()
+    }
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, PartialOrd, Ord, Debug)]
+pub enum BlizzardCell {
+    Wall,
+    Wind(Wind),
+}
+
+impl BlizzardCell {
+    fn add_wind(&mut self, wind_dir: ManhattanDir) {
+        match self {
+            Self::Wall => {
+                panic!("Illegal operation")
+            }
+            Self::Wind(w) => {
+                w.add_wind(wind_dir);
+            }
+        }
+    }
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, PartialOrd, Ord, Debug, Default)]
+pub struct Wind {
+    has_wind: [bool; 4],
+}
+
+impl Wind {
+    fn dir(dir: ManhattanDir) -> Self {
+        let mut result = Self::default();
+        result.has_wind[dir as usize] = true;
+        result
+    }
+}
+
+impl Display for Wind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let winds = self.winds();
+        let count_str = format!("{}", winds.len());
+        let s = match winds.len() {
+            0 => ".",
+            1 => match winds[0] {
+                ManhattanDir::N => "^",
+                ManhattanDir::E => ">",
+                ManhattanDir::S => "v",
+                ManhattanDir::W => "<",
+            },
+            _ => count_str.as_str(),
+        };
+        write!(f, "{s}")
+    }
+}
+
+impl Wind {
+    fn is_windy(&self) -> bool {
+        self.has_wind.iter().any(|w| *w)
+    }
+
+    fn winds(&self) -> Vec<ManhattanDir> {
+        all::<ManhattanDir>()
+            .filter(|d| self.has_wind[*d as usize])
+            .collect()
+    }
+
+    fn add_wind(&mut self, wind_dir: ManhattanDir) {
+        self.has_wind[wind_dir as usize] = true;
+    }
+}
+
+impl Display for BlizzardCell {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BlizzardCell::Wall => write!(f, "#"),
+            BlizzardCell::Wind(w) => write!(f, "{}", *w),
+        }
+    }
+}
+
+impl BlizzardCell {
+    pub fn from_char(value: char) -> Option<Self> {
+        match value {
+            '.' => Some(Self::Wind(Wind::default())),
+            '#' => Some(Self::Wall),
+            '^' => Some(Self::Wind(Wind::dir(ManhattanDir::N))),
+            '>' => Some(Self::Wind(Wind::dir(ManhattanDir::E))),
+            'v' => Some(Self::Wind(Wind::dir(ManhattanDir::S))),
+            '<' => Some(Self::Wind(Wind::dir(ManhattanDir::W))),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BlizzardMap {
+    wind_map: BTreeMap<Pt, BlizzardCell>,
+    width: isize,
+    height: isize,
+}
+
+impl BlizzardMap {
+    pub fn from_file(filename: &str) -> anyhow::Result<Self> {
+        Self::from_input(&Input::from_file(filename)?)
+    }
+
+    pub fn from_input(input: &Input) -> anyhow::Result<Self> {
+        let mut wind_map = BTreeMap::new();
+        let mut height = 0;
+        let mut width = 0;
+        parse_lines(input, |line| {
+            for (col, c) in line.chars().enumerate() {
+                let cell = BlizzardCell::from_char(c).ok_or_else(|| {
+                    LineError::new(col + 1, "#, ., ^, >, v or <", c.to_string().as_str())
+                })?;
+                width = max(width, col as isize + 1);
+                wind_map.insert(Pt::new([col as isize, height]), cell);
+            }
+            height += 1;
+            Ok(())
+        })?;
+        Ok(Self {
+            wind_map,
+            width,
+            height,
+        })
+    }
+
+    /// Minutes until the blizzards are all back where they started.
+    pub fn period(&self) -> anyhow::Result<usize> {
+        Ok(CycleDetector::default().find(self)?.length)
+    }
+
+    pub fn can_enter(&self, p: Pt) -> bool {
+        self.wind_map.get(&p).map_or(false, |c| match c {
+            BlizzardCell::Wall => false,
+            BlizzardCell::Wind(w) => !w.is_windy(),
+        })
+    }
+
+    pub fn entrance(&self) -> Pt {
+        self.empty_cell_in(0)
+    }
+
+    pub fn exit(&self) -> Pt {
+        self.empty_cell_in(self.height - 1)
+    }
+
+    fn empty_cell_in(&self, row: isize) -> Pt {
+        self.wind_map
+            .keys()
+            .find(|p| p[1] == row && self.can_enter(**p))
+            .copied()
+            .unwrap()
+    }
+
+    pub fn next_step(&self) -> Self {
+        let mut next = Self {
+            width: self.width,
+            height: self.height,
+            wind_map: self
+                .wind_map
+                .iter()
+                .map(|(p, c)| {
+                    (
+                        *p,
+                        match c {
+                            BlizzardCell::Wall => *c,
+                            BlizzardCell::Wind(_) => BlizzardCell::Wind(Wind::default()),
+                        },
+                    )
+                })
+                .collect(),
+        };
+        for (p, cell) in self.wind_map.iter() {
+            if let BlizzardCell::Wind(w) = cell {
+                for wind_dir in w.winds() {
+                    let next_p = self.wind_next(wind_dir, *p);
+                    next.wind_map.get_mut(&next_p).unwrap().add_wind(wind_dir);
+                }
+            }
+        }
+        next
+    }
+
+    fn wind_next(&self, wind_dir: ManhattanDir, wind_pos: Pt) -> Pt {
+        let mut updated = wind_pos.manhattan_moved(wind_dir);
+        let max_x = self.width - 2;
+        let max_y = self.height - 2;
+        if updated[0] == 0 {
+            updated[0] = max_x;
+        }
+        if updated[1] == 0 {
+            updated[1] = max_y;
+        }
+        if updated[0] > max_x {
+            updated[0] = 1;
+        }
+        if updated[1] > max_y {
+            updated[1] = 1;
+        }
+        updated
+    }
+}
+
+impl Display for BlizzardMap {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in 0..self.height {
+            for col in 0..self.width {
+                write!(f, "{}", self.wind_map.get(&Pt::new([col, row])).unwrap())?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::day24::BlizzardMap;
+
+    #[test]
+    fn test_period() {
+        let map = BlizzardMap::from_file("ex/day24b.txt").unwrap();
+        assert_eq!(map.period().unwrap(), 12);
+    }
+}
@@ -0,0 +1,100 @@
+use std::{collections::VecDeque, fmt::Display, iter::Sum, str::FromStr};
+
+use anyhow::bail;
+
+use crate::{
+    input::Input,
+    parse::{parse_lines, LineError},
+    Solution,
+};
+
+#[derive(Clone, Debug)]
+pub struct FuelRequirements {
+    snafus: Vec<Snafu>,
+}
+
+impl FuelRequirements {
+    pub fn from_file(filename: &str) -> anyhow::Result<Self> {
+        Self::from_input(&Input::from_file(filename)?)
+    }
+
+    pub fn from_input(input: &Input) -> anyhow::Result<Self> {
+        Ok(Self {
+            snafus: parse_lines(input, |line| line.parse())?,
+        })
+    }
+}
+
+impl Solution for FuelRequirements {
+    type Part1 = Snafu;
+    type Part2 = Snafu;
+
+    const HAS_PART_2: bool = false;
+
+    fn load(input: &Input) -> anyhow::Result<Self> {
+        Self::from_input(input)
+    }
+
+    fn part1(&self) -> anyhow::Result<Snafu> {
+        Ok(self.snafus.iter().copied().sum())
+    }
+
+    fn part2(&self) -> anyhow::Result<Snafu> {
+        bail!("Day 25 has no part 2")
+    }
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct Snafu(pub i64);
+
+impl Sum<Snafu> for Snafu {
+    fn sum<I: Iterator<Item = Snafu>>(iter: I) -> Self {
+        Snafu(iter.map(|s| s.0).sum())
+    }
+}
+
+impl FromStr for Snafu {
+    type Err = LineError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Err(LineError::new(1, "a SNAFU digit", "end of line"));
+        }
+        let mut total = 0;
+        for (i, c) in s.char_indices() {
+            total *= 5;
+            total += match c {
+                '=' => -2,
+                '-' => -1,
+                '0'..='2' => (c as u8 - b'0') as i64,
+                _ => {
+                    return Err(LineError::new(
+                        i + 1,
+                        "=, -, 0, 1 or 2",
+                        c.to_string().as_str(),
+                    ))
+                }
+            };
+        }
+        Ok(Self(total))
+    }
+}
+
+impl Display for Snafu {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut digits = VecDeque::new();
+        let mut value = self.0;
+        while value > 0 {
+            let digit = value % 5;
+            value /= 5;
+            digits.push_front(if digit < 3 {
+                format!("{digit}")
+            } else {
+                value += 1;
+                (if digit == 3 { "=" } else { "-" }).to_owned()
+            });
+        }
+        let result = digits.iter().cloned().collect::<String>();
+        write!(f, "{result}")
+    }
+}
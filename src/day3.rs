@@ -0,0 +1,606 @@
+use std::{fmt::Display, iter::repeat_n};
+
+use anyhow::{anyhow, bail};
+
+use crate::{
+    input::Input,
+    parse::{parse_lines, LineError},
+    Solution,
+};
+
+/// How many elves share each badge.
+const GROUP_SIZE: usize = 3;
+
+#[derive(Clone, Debug)]
+pub struct Rucksacks {
+    lines: Vec<String>,
+}
+
+impl Rucksacks {
+    pub fn from_file(filename: &str) -> anyhow::Result<Self> {
+        Self::from_input(&Input::from_file(filename)?)
+    }
+
+    pub fn from_input(input: &Input) -> anyhow::Result<Self> {
+        Ok(Self {
+            lines: parse_lines(input, |line| {
+                if let Some((i, c)) = line.char_indices().find(|(_, c)| !c.is_ascii_alphabetic()) {
+                    Err(LineError::new(
+                        i + 1,
+                        "an item letter",
+                        c.to_string().as_str(),
+                    ))
+                } else if line.len() % 2 == 1 {
+                    Err(LineError::new(
+                        line.len() + 1,
+                        "an even number of items",
+                        format!("{} items", line.len()).as_str(),
+                    ))
+                } else {
+                    Ok(line.to_owned())
+                }
+            })?,
+        })
+    }
+}
+
+impl Rucksacks {
+    /// The item each rucksack has in both compartments.
+    pub fn misplaced(&self) -> anyhow::Result<Vec<char>> {
+        self.lines
+            .iter()
+            .enumerate()
+            .map(|(i, line)| {
+                let (left, right) = compartments(line.as_str())?;
+                only_item(left.intersection(right))
+                    .map_err(|e| anyhow!("Rucksack {}: {e} in both compartments", i + 1))
+            })
+            .collect()
+    }
+
+    /// The badge each group of `group_size` consecutive rucksacks shares.
+    pub fn badges(&self, group_size: usize) -> anyhow::Result<Vec<char>> {
+        if group_size == 0 || !self.lines.len().is_multiple_of(group_size) {
+            bail!(
+                "{} rucksacks do not divide into groups of {group_size}",
+                self.lines.len()
+            );
+        }
+        self.lines
+            .chunks(group_size)
+            .enumerate()
+            .map(|(i, group)| {
+                let first = i * group_size + 1;
+                let sets = group
+                    .iter()
+                    .map(|l| ItemSet::from_items(l.as_str()))
+                    .collect::<anyhow::Result<Vec<_>>>()?;
+                only_item(common(sets.into_iter())).map_err(|e| {
+                    anyhow!("Rucksacks {first}-{}: {e} shared", first + group_size - 1)
+                })
+            })
+            .collect()
+    }
+}
+
+impl Rucksacks {
+    /// Rewrites the rucksacks so that none has an item type in both
+    /// compartments, while each group of `group_size` still shares its one
+    /// badge, moving as few items as possible.
+    pub fn repair(&self, group_size: usize) -> anyhow::Result<RepairPlan> {
+        let badges = self.badges(group_size)?;
+        let mut plan = RepairPlan::default();
+        for (g, group) in self.lines.chunks(group_size).enumerate() {
+            let first = g * group_size;
+            let repair = repair_group(group, badges[g])
+                .map_err(|e| anyhow!("Rucksacks {}-{}: {e}", first + 1, first + group_size))?;
+            plan.lines.extend(repair.lines);
+            plan.moves += repair.moves;
+            plan.transfers
+                .extend(repair.transfers.iter().map(|transfer| ItemMove {
+                    from: transfer.from + first,
+                    to: transfer.to + first,
+                    ..*transfer
+                }));
+        }
+        Ok(plan)
+    }
+}
+
+impl Solution for Rucksacks {
+    type Part1 = i64;
+    type Part2 = i64;
+
+    fn load(input: &Input) -> anyhow::Result<Self> {
+        Self::from_input(input)
+    }
+
+    fn part1(&self) -> anyhow::Result<i64> {
+        self.misplaced()?.into_iter().map(score).sum()
+    }
+
+    fn part2(&self) -> anyhow::Result<i64> {
+        self.badges(GROUP_SIZE)?.into_iter().map(score).sum()
+    }
+}
+
+pub fn score(c: char) -> anyhow::Result<i64> {
+    match c {
+        'a'..='z' => Ok(c as i64 - 'a' as i64 + 1),
+        'A'..='Z' => Ok(c as i64 - 'A' as i64 + 27),
+        _ => bail!("{c:?} is not an item"),
+    }
+}
+
+/// The bit or count index for `item`, one less than its priority.
+fn index(item: char) -> anyhow::Result<usize> {
+    Ok(score(item)? as usize - 1)
+}
+
+/// The item types in a rucksack or compartment, as bits indexed by priority.
+#[derive(Copy, Clone, Default, Debug, PartialEq, Eq)]
+pub struct ItemSet(u64);
+
+impl ItemSet {
+    pub fn from_items(items: &str) -> anyhow::Result<Self> {
+        items.chars().try_fold(Self::default(), Self::with)
+    }
+
+    pub fn all() -> Self {
+        Self((1 << 52) - 1)
+    }
+
+    pub fn intersection(self, other: Self) -> Self {
+        Self(self.0 & other.0)
+    }
+
+    pub fn union(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+
+    pub fn with(self, item: char) -> anyhow::Result<Self> {
+        Ok(Self(self.0 | 1 << index(item)?))
+    }
+
+    /// Whether the set holds `item`, which is never true of a non-item.
+    pub fn contains(self, item: char) -> bool {
+        index(item).is_ok_and(|i| self.0 & 1 << i != 0)
+    }
+
+    pub fn len(self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// The items in priority order.
+    pub fn items(self) -> impl Iterator<Item = char> {
+        ('a'..='z')
+            .chain('A'..='Z')
+            .filter(move |c| self.contains(*c))
+    }
+}
+
+impl Display for ItemSet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.items().collect::<String>())
+    }
+}
+
+/// The items every set has.
+pub fn common<I: Iterator<Item = ItemSet>>(sets: I) -> ItemSet {
+    sets.fold(ItemSet::all(), ItemSet::intersection)
+}
+
+/// The one item in `items`, or an error saying how many there are instead.
+pub fn only_item(items: ItemSet) -> anyhow::Result<char> {
+    match items.len() {
+        1 => Ok(items.items().next().unwrap()),
+        0 => bail!("no item is"),
+        n => bail!("{n} items ({items}) are"),
+    }
+}
+
+/// Splits a line into its two compartments, checking that it holds only items.
+fn halves(line: &str) -> anyhow::Result<(&str, &str)> {
+    if let Some(c) = line.chars().find(|c| !c.is_ascii_alphabetic()) {
+        bail!("{c:?} is not an item");
+    }
+    Ok(line.split_at(line.len() / 2))
+}
+
+pub fn compartments(line: &str) -> anyhow::Result<(ItemSet, ItemSet)> {
+    let (left, right) = halves(line)?;
+    Ok((ItemSet::from_items(left)?, ItemSet::from_items(right)?))
+}
+
+/// Rearranges a rucksack so that each item type is in only one compartment,
+/// keeping the compartments the same size. Returns the new line and the
+/// fewest items that have to change compartment, or `None` if the types
+/// cannot be split evenly.
+pub fn repair_rucksack(line: &str) -> anyhow::Result<Option<(String, usize)>> {
+    let (left, right) = halves(line)?;
+    let half = left.len();
+    let (left, right) = (item_counts(left)?, item_counts(right)?);
+    // The fewest moves that put `size` items on the left, for each size,
+    // along with the types that go there.
+    let mut best = vec![None; half + 1];
+    best[0] = Some((0, ItemSet::default()));
+    for item in ItemSet::all().items() {
+        let (left, right) = (left[index(item)?], right[index(item)?]);
+        if left + right == 0 {
+            continue;
+        }
+        let mut next: Vec<Option<(usize, ItemSet)>> = vec![None; half + 1];
+        for (size, choice) in best.iter().enumerate() {
+            if let Some((moves, chosen)) = *choice {
+                let mut consider = |size: usize, option: (usize, ItemSet)| {
+                    if next[size].is_none_or(|(fewest, _)| option.0 < fewest) {
+                        next[size] = Some(option);
+                    }
+                };
+                consider(size, (moves + left, chosen));
+                if size + left + right <= half {
+                    consider(size + left + right, (moves + right, chosen.with(item)?));
+                }
+            }
+        }
+        best = next;
+    }
+    Ok(best[half].map(|(moves, left_types)| {
+        let (left, right): (String, String) = line.chars().partition(|c| left_types.contains(*c));
+        (left + right.as_str(), moves)
+    }))
+}
+
+/// One item moving to another rucksack in the same group, numbered from 0.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ItemMove {
+    pub item: char,
+    pub from: usize,
+    pub to: usize,
+}
+
+impl Display for ItemMove {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "move {} from rucksack {} to rucksack {}",
+            self.item,
+            self.from + 1,
+            self.to + 1
+        )
+    }
+}
+
+/// Rewritten rucksack lines with no item type in both compartments.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RepairPlan {
+    pub lines: Vec<String>,
+    /// Items that changed compartment, whether or not they changed rucksack.
+    pub moves: usize,
+    /// The moves that take an item to another rucksack.
+    pub transfers: Vec<ItemMove>,
+}
+
+/// How many candidate repairs a group may try before giving up.
+const MAX_REPAIR_CANDIDATES: usize = 1_000_000;
+
+const ITEM_TYPES: usize = 52;
+
+/// How many of each item type a compartment holds, indexed by priority.
+type ItemCounts = [usize; ITEM_TYPES];
+
+fn item_counts(items: &str) -> anyhow::Result<ItemCounts> {
+    let mut counts = [0; ITEM_TYPES];
+    for c in items.chars() {
+        counts[index(c)?] += 1;
+    }
+    Ok(counts)
+}
+
+fn item(priority: usize) -> char {
+    ItemSet::all().items().nth(priority).unwrap()
+}
+
+/// Searches for the fewest item moves that repair a group, where each
+/// compartment keeps its size and `badge` stays the only item type in every
+/// rucksack. A repair keeps some items where they are and places the rest in
+/// the space that leaves, so candidates are tried in order of how many items
+/// they take out. Compartments are numbered `2 * rucksack + side`.
+struct RepairSearch {
+    start: Vec<ItemCounts>,
+    sizes: Vec<usize>,
+    badge: usize,
+    candidates: usize,
+}
+
+impl RepairSearch {
+    fn new(group: &[String], badge: char) -> anyhow::Result<Self> {
+        let mut search = Self {
+            start: vec![],
+            sizes: vec![],
+            badge: index(badge)?,
+            candidates: 0,
+        };
+        for line in group {
+            let (left, right) = halves(line)?;
+            for half in [left, right] {
+                search.start.push(item_counts(half)?);
+                search.sizes.push(half.len());
+            }
+        }
+        Ok(search)
+    }
+
+    /// Item counts for each compartment after the cheapest repair.
+    fn run(&mut self) -> anyhow::Result<Vec<ItemCounts>> {
+        // Each type in both compartments of a rucksack must leave one of them.
+        let conflicts = (0..self.sizes.len() / 2)
+            .flat_map(|r| (0..ITEM_TYPES).map(move |t| (r, t)))
+            .filter(|(r, t)| self.start[2 * r][*t] > 0 && self.start[2 * r + 1][*t] > 0)
+            .collect::<Vec<_>>();
+        let total = self.sizes.iter().sum();
+        for cost in 0..=total {
+            for sides in 0..1usize << conflicts.len() {
+                let mut kept = self.start.clone();
+                let mut taken = 0;
+                for (i, (r, t)) in conflicts.iter().enumerate() {
+                    let side = 2 * r + (sides >> i & 1);
+                    taken += kept[side][*t];
+                    kept[side][*t] = 0;
+                }
+                if taken <= cost {
+                    if let Some(repaired) = self.take(&mut kept, cost - taken, 0)? {
+                        return Ok(repaired);
+                    }
+                }
+            }
+        }
+        bail!("No repair keeps exactly one badge")
+    }
+
+    /// Tries taking `count` more items out of `kept`, from compartment and
+    /// type index `from` onwards so that each choice is tried only once.
+    fn take(
+        &mut self,
+        kept: &mut [ItemCounts],
+        count: usize,
+        from: usize,
+    ) -> anyhow::Result<Option<Vec<ItemCounts>>> {
+        if count == 0 {
+            self.candidates += 1;
+            if self.candidates > MAX_REPAIR_CANDIDATES {
+                bail!("No repair found within {MAX_REPAIR_CANDIDATES} candidates");
+            }
+            return Ok(self.place(kept));
+        }
+        for index in from..kept.len() * ITEM_TYPES {
+            let (half, t) = (index / ITEM_TYPES, index % ITEM_TYPES);
+            if kept[half][t] > 0 {
+                kept[half][t] -= 1;
+                let repaired = self.take(kept, count - 1, index)?;
+                kept[half][t] += 1;
+                if repaired.is_some() {
+                    return Ok(repaired);
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    /// Puts the items missing from `kept` back into the space left for them.
+    fn place(&self, kept: &[ItemCounts]) -> Option<Vec<ItemCounts>> {
+        let mut items = vec![];
+        for t in 0..ITEM_TYPES {
+            let start = self.start.iter().map(|half| half[t]).sum::<usize>();
+            let left = kept.iter().map(|half| half[t]).sum::<usize>();
+            if start > left {
+                items.push((t, start - left));
+            }
+        }
+        let mut space = self
+            .sizes
+            .iter()
+            .zip(kept.iter())
+            .map(|(size, half)| size - half.iter().sum::<usize>())
+            .collect::<Vec<_>>();
+        let mut placed = kept.to_vec();
+        self.spread(&mut placed, &mut space, &items, 0)
+            .then_some(placed)
+    }
+
+    /// Places `items[i..]`, spreading each type over the compartments from
+    /// `half` onwards.
+    fn spread(
+        &self,
+        placed: &mut [ItemCounts],
+        space: &mut [usize],
+        items: &[(usize, usize)],
+        half: usize,
+    ) -> bool {
+        let Some(((t, count), rest)) = items.split_first() else {
+            return true;
+        };
+        if half == placed.len() {
+            return *count == 0 && self.allowed(placed, *t) && self.spread(placed, space, rest, 0);
+        }
+        let blocked = placed[half ^ 1][*t] > 0;
+        let most = if blocked {
+            0
+        } else {
+            (*count).min(space[half])
+        };
+        for n in (0..=most).rev() {
+            placed[half][*t] += n;
+            space[half] -= n;
+            let mut items = items.to_vec();
+            items[0].1 -= n;
+            let done = self.spread(placed, space, &items, half + 1);
+            if done {
+                return true;
+            }
+            placed[half][*t] -= n;
+            space[half] += n;
+        }
+        false
+    }
+
+    /// Whether type `t` is in every rucksack if and only if it is the badge.
+    fn allowed(&self, placed: &[ItemCounts], t: usize) -> bool {
+        let rucksacks = placed
+            .chunks(2)
+            .filter(|halves| halves[0][t] + halves[1][t] > 0)
+            .count();
+        (rucksacks == placed.len() / 2) == (t == self.badge)
+    }
+}
+
+/// The fewest moves that leave no item type in both compartments of any
+/// rucksack in `group`, while `badge` stays the only type they all share.
+/// Items may move between compartments or to another rucksack in the group,
+/// but every compartment keeps its size.
+fn repair_group(group: &[String], badge: char) -> anyhow::Result<RepairPlan> {
+    let mut search = RepairSearch::new(group, badge)?;
+    let repaired = search.run()?;
+    let start = &search.start;
+    let mut plan = RepairPlan::default();
+    for (h, half) in repaired.iter().enumerate() {
+        plan.moves += (0..ITEM_TYPES)
+            .map(|t| start[h][t].saturating_sub(half[t]))
+            .sum::<usize>();
+    }
+    for t in 0..ITEM_TYPES {
+        let change = |r: usize| {
+            let count = |halves: &[ItemCounts]| halves[2 * r][t] + halves[2 * r + 1][t];
+            count(&repaired) as isize - count(start) as isize
+        };
+        let mut gains = (0..group.len())
+            .flat_map(|r| repeat_n(r, change(r).max(0) as usize))
+            .collect::<Vec<_>>();
+        for from in 0..group.len() {
+            for _ in 0..(-change(from)).max(0) {
+                plan.transfers.push(ItemMove {
+                    item: item(t),
+                    from,
+                    to: gains.remove(0),
+                });
+            }
+        }
+    }
+    for (r, line) in group.iter().enumerate() {
+        let (left, right) = halves(line)?;
+        let mut rewritten = String::new();
+        for (h, items) in [(2 * r, left), (2 * r + 1, right)] {
+            // Items that stay keep their order; arrivals follow them.
+            let mut staying = [0; ITEM_TYPES];
+            for t in 0..ITEM_TYPES {
+                staying[t] = start[h][t].min(repaired[h][t]);
+            }
+            for c in items.chars() {
+                let t = index(c)?;
+                if staying[t] > 0 {
+                    staying[t] -= 1;
+                    rewritten.push(c);
+                }
+            }
+            for t in 0..ITEM_TYPES {
+                let arriving = repaired[h][t].saturating_sub(start[h][t]);
+                rewritten.extend(repeat_n(item(t), arriving));
+            }
+        }
+        plan.lines.push(rewritten);
+    }
+    Ok(plan)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        day3::{common, compartments, repair_rucksack, ItemSet, Rucksacks},
+        input::Input,
+    };
+
+    #[test]
+    fn test_item_set() {
+        let (left, right) = compartments("vJrwpWtwJgWrhcsFMMfFFhFp").unwrap();
+        assert_eq!(left.intersection(right).to_string(), "p");
+        let set = ItemSet::from_items("aAzZa").unwrap();
+        assert_eq!((set.len(), set.to_string()), (4, "azAZ".to_owned()));
+        assert!(common([].into_iter()) == ItemSet::all());
+
+        assert_eq!(
+            ItemSet::from_items("ab1").unwrap_err().to_string(),
+            "'1' is not an item"
+        );
+        assert!(set.with('!').is_err());
+        assert!(!set.contains('!'));
+        assert!(compartments("aébc").is_err());
+        assert!(repair_rucksack("a b ").is_err());
+    }
+
+    #[test]
+    fn test_badges() {
+        let rucksacks = Rucksacks::from_file("ex/day3.txt").unwrap();
+        assert_eq!(rucksacks.badges(3).unwrap(), vec!['r', 'Z']);
+        let error = rucksacks.badges(1).unwrap_err().to_string();
+        assert!(error.starts_with("Rucksacks 1-1: "), "{error}");
+        assert!(rucksacks.badges(4).is_err());
+
+        let input = Input::from("abAB\nabCD\nabEF");
+        let error = Rucksacks::from_input(&input)
+            .unwrap()
+            .badges(3)
+            .unwrap_err();
+        assert_eq!(error.to_string(), "Rucksacks 1-3: 2 items (ab) are shared");
+        let error = Rucksacks::from_input(&input)
+            .unwrap()
+            .misplaced()
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Rucksack 1: no item is in both compartments"
+        );
+    }
+
+    #[test]
+    fn test_repair() {
+        assert_eq!(
+            repair_rucksack("abac").unwrap(),
+            Some(("bcaa".to_owned(), 2))
+        );
+        assert_eq!(repair_rucksack("aaab").unwrap(), None);
+
+        let rucksacks = Rucksacks::from_input(&Input::from("aaab\nbcbc\nbdbd")).unwrap();
+        let plan = rucksacks.repair(3).unwrap();
+        assert_eq!(plan.transfers.len(), 4);
+        for line in plan.lines.iter() {
+            let (left, right) = compartments(line.as_str()).unwrap();
+            assert!(left.intersection(right).is_empty(), "{line}");
+        }
+        let repaired = Rucksacks {
+            lines: plan.lines.clone(),
+        };
+        assert_eq!(repaired.badges(3).unwrap(), vec!['b']);
+        assert_eq!(plan.moves, 1 + 2 + 2);
+
+        // No rucksack can be repaired alone, so each passes an item on.
+        let rucksacks = Rucksacks::from_input(&Input::from("aaab\ncccb\ndddb")).unwrap();
+        let plan = rucksacks.repair(3).unwrap();
+        assert_eq!(plan.moves, 3);
+        assert_eq!(
+            plan.transfers
+                .iter()
+                .map(|transfer| transfer.to_string())
+                .collect::<Vec<_>>(),
+            vec![
+                "move a from rucksack 1 to rucksack 2",
+                "move c from rucksack 2 to rucksack 3",
+                "move d from rucksack 3 to rucksack 1",
+            ]
+        );
+        assert_eq!(plan.lines, vec!["aabd", "ccba", "ddbc"]);
+    }
+}
@@ -0,0 +1,362 @@
+use std::{
+    cmp::Reverse, collections::BinaryHeap, fmt::Display, iter::FromIterator, ops::RangeInclusive,
+};
+
+use crate::{
+    input::Input,
+    parse::{column_of, parse_lines, parse_token, LineError, LineResult},
+    Solution,
+};
+
+#[derive(Clone, Debug)]
+pub struct Assignments {
+    pairs: Vec<(RangeInclusive<i64>, RangeInclusive<i64>)>,
+}
+
+impl Assignments {
+    pub fn from_file(filename: &str) -> anyhow::Result<Self> {
+        Self::from_input(&Input::from_file(filename)?)
+    }
+
+    pub fn from_input(input: &Input) -> anyhow::Result<Self> {
+        Ok(Self {
+            pairs: parse_lines(input, parse_ranges)?,
+        })
+    }
+
+    /// Every elf's sections; the elves in pair `i` are `2 * i` and `2 * i + 1`.
+    pub fn elves(&self) -> Vec<RangeInclusive<i64>> {
+        self.pairs
+            .iter()
+            .flat_map(|(elf1, elf2)| [elf1.clone(), elf2.clone()])
+            .collect()
+    }
+}
+
+impl Solution for Assignments {
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn load(input: &Input) -> anyhow::Result<Self> {
+        Self::from_input(input)
+    }
+
+    fn part1(&self) -> anyhow::Result<usize> {
+        Ok(self
+            .pairs
+            .iter()
+            .filter(|(elf1, elf2)| fully_contains(elf1, elf2) || fully_contains(elf2, elf1))
+            .count())
+    }
+
+    fn part2(&self) -> anyhow::Result<usize> {
+        Ok(self
+            .pairs
+            .iter()
+            .filter(|(elf1, elf2)| overlap(elf1, elf2))
+            .count())
+    }
+
+    fn diagnostics(&self) -> Vec<(String, String)> {
+        let elves = self.elves();
+        let coverage = Coverage::new(&elves);
+        let redundant = coverage
+            .redundant(&elves)
+            .iter()
+            .map(|elf| elf.to_string())
+            .collect::<Vec<_>>();
+        vec![
+            ("elves".to_owned(), format!("{}", elves.len())),
+            (
+                "overlapping pairs".to_owned(),
+                format!("{}", overlapping_pairs(&elves).len()),
+            ),
+            (
+                "sections covered by 3+ elves".to_owned(),
+                format!("{}", coverage.at_least(3)),
+            ),
+            ("redundant elves".to_owned(), redundant.join(" ")),
+        ]
+    }
+}
+
+pub fn parse_ranges(line: &str) -> LineResult<(RangeInclusive<i64>, RangeInclusive<i64>)> {
+    let (left, right) = line
+        .split_once(',')
+        .ok_or_else(|| LineError::new(column_of(line, line.len()), "\",\"", "end of line"))?;
+    Ok((
+        parse_range(left)?,
+        parse_range(right).map_err(|e| e.shifted(left.chars().count() + 1))?,
+    ))
+}
+
+pub fn parse_range(range: &str) -> LineResult<RangeInclusive<i64>> {
+    let (start, end) = range
+        .split_once('-')
+        .ok_or_else(|| LineError::new(column_of(range, range.len()), "\"-\"", "end of range"))?;
+    let start = parse_token(1, start, "a section number")?;
+    let end = parse_token(
+        column_of(range, range.len() - end.len()),
+        end,
+        "a section number",
+    )?;
+    Ok(start..=end)
+}
+
+pub fn fully_contains(container: &RangeInclusive<i64>, containee: &RangeInclusive<i64>) -> bool {
+    container.contains(containee.start()) && container.contains(containee.end())
+}
+
+pub fn overlap(elf1: &RangeInclusive<i64>, elf2: &RangeInclusive<i64>) -> bool {
+    elf1.contains(elf2.start())
+        || elf1.contains(elf2.end())
+        || elf2.contains(elf1.start())
+        || elf2.contains(elf1.end())
+}
+
+/// A set of sections, kept as sorted ranges that neither overlap nor touch.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct IntervalSet {
+    ranges: Vec<RangeInclusive<i64>>,
+}
+
+impl IntervalSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn ranges(&self) -> &[RangeInclusive<i64>] {
+        &self.ranges
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// The number of sections in the set.
+    pub fn len(&self) -> i64 {
+        self.ranges.iter().map(|r| r.end() - r.start() + 1).sum()
+    }
+
+    pub fn contains(&self, section: i64) -> bool {
+        self.contains_range(&(section..=section))
+    }
+
+    /// True if every section of `range` is in the set.
+    pub fn contains_range(&self, range: &RangeInclusive<i64>) -> bool {
+        if range.is_empty() {
+            return true;
+        }
+        let i = self.ranges.partition_point(|r| r.end() < range.start());
+        self.ranges.get(i).is_some_and(|r| fully_contains(r, range))
+    }
+
+    pub fn insert(&mut self, range: RangeInclusive<i64>) {
+        if range.is_empty() {
+            return;
+        }
+        let first = self
+            .ranges
+            .partition_point(|r| r.end() + 1 < *range.start());
+        let last = self
+            .ranges
+            .partition_point(|r| *r.start() <= range.end() + 1);
+        let (mut start, mut end) = range.into_inner();
+        if first < last {
+            start = start.min(*self.ranges[first].start());
+            end = end.max(*self.ranges[last - 1].end());
+        }
+        self.ranges.splice(first..last, [start..=end]);
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let mut ranges = self
+            .ranges
+            .iter()
+            .chain(other.ranges.iter())
+            .cloned()
+            .collect::<Vec<_>>();
+        ranges.sort_by_key(|r| *r.start());
+        ranges.into_iter().collect()
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut ranges = vec![];
+        let (mut i, mut j) = (0, 0);
+        while i < self.ranges.len() && j < other.ranges.len() {
+            let (a, b) = (&self.ranges[i], &other.ranges[j]);
+            let common = *a.start().max(b.start())..=*a.end().min(b.end());
+            if !common.is_empty() {
+                ranges.push(common);
+            }
+            if a.end() < b.end() {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        Self { ranges }
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut ranges = vec![];
+        let mut j = 0;
+        for range in self.ranges.iter() {
+            let mut start = *range.start();
+            while j < other.ranges.len() && other.ranges[j].end() < range.start() {
+                j += 1;
+            }
+            let mut k = j;
+            while k < other.ranges.len() && other.ranges[k].start() <= range.end() {
+                let removed = &other.ranges[k];
+                if *removed.start() > start {
+                    ranges.push(start..=removed.start() - 1);
+                }
+                start = start.max(removed.end() + 1);
+                k += 1;
+            }
+            if start <= *range.end() {
+                ranges.push(start..=*range.end());
+            }
+        }
+        Self { ranges }
+    }
+}
+
+impl FromIterator<RangeInclusive<i64>> for IntervalSet {
+    fn from_iter<I: IntoIterator<Item = RangeInclusive<i64>>>(iter: I) -> Self {
+        let mut set = Self::new();
+        for range in iter {
+            set.insert(range);
+        }
+        set
+    }
+}
+
+impl Display for IntervalSet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let ranges = self
+            .ranges
+            .iter()
+            .map(|r| format!("{}-{}", r.start(), r.end()))
+            .collect::<Vec<_>>();
+        write!(f, "{}", ranges.join(","))
+    }
+}
+
+/// How many elves cover each section, as consecutive ranges with their counts.
+/// Sections covered by no elf are left out.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Coverage {
+    counts: Vec<(RangeInclusive<i64>, usize)>,
+}
+
+impl Coverage {
+    /// Sweeps across the start and end of every range.
+    pub fn new(ranges: &[RangeInclusive<i64>]) -> Self {
+        let mut events = ranges
+            .iter()
+            .filter(|r| !r.is_empty())
+            .flat_map(|r| [(*r.start(), 1), (r.end() + 1, -1)])
+            .collect::<Vec<(i64, i64)>>();
+        events.sort_unstable();
+        let mut counts = vec![];
+        let mut active = 0;
+        for (i, (section, change)) in events.iter().enumerate() {
+            active += change;
+            match events.get(i + 1) {
+                Some((next, _)) if next > section && active > 0 => {
+                    counts.push((*section..=next - 1, active as usize));
+                }
+                _ => {}
+            }
+        }
+        Self { counts }
+    }
+
+    pub fn counts(&self) -> &[(RangeInclusive<i64>, usize)] {
+        &self.counts
+    }
+
+    /// The sections covered by `k` or more elves.
+    pub fn at_least(&self, k: usize) -> IntervalSet {
+        self.counts
+            .iter()
+            .filter(|(_, count)| *count >= k)
+            .map(|(range, _)| range.clone())
+            .collect()
+    }
+
+    /// The elves (indices into `ranges`, which must be the ranges this was
+    /// built from) whose every section is also covered by another elf.
+    pub fn redundant(&self, ranges: &[RangeInclusive<i64>]) -> Vec<usize> {
+        let shared = self.at_least(2);
+        (0..ranges.len())
+            .filter(|i| shared.contains_range(&ranges[*i]))
+            .collect()
+    }
+}
+
+/// Every pair `(i, j)` with `i < j` of ranges sharing a section, in order.
+/// Sweeps across the ranges by start, keeping those still open in a heap
+/// ordered by end.
+pub fn overlapping_pairs(ranges: &[RangeInclusive<i64>]) -> Vec<(usize, usize)> {
+    let mut order = (0..ranges.len())
+        .filter(|i| !ranges[*i].is_empty())
+        .collect::<Vec<_>>();
+    order.sort_by_key(|i| *ranges[*i].start());
+    let mut open = BinaryHeap::new();
+    let mut pairs = vec![];
+    for i in order {
+        while open
+            .peek()
+            .is_some_and(|Reverse((end, _))| end < ranges[i].start())
+        {
+            open.pop();
+        }
+        for Reverse((_, j)) in open.iter() {
+            pairs.push((i.min(*j), i.max(*j)));
+        }
+        open.push(Reverse((*ranges[i].end(), i)));
+    }
+    pairs.sort_unstable();
+    pairs
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::day4::{overlapping_pairs, Assignments, Coverage, IntervalSet};
+
+    #[test]
+    fn test_interval_set() {
+        let a = [1..=3, 10..=12, 5..=6, 4..=4]
+            .into_iter()
+            .collect::<IntervalSet>();
+        let b = [2..=5, 11..=20].into_iter().collect::<IntervalSet>();
+        assert_eq!(a.to_string(), "1-6,10-12");
+        assert_eq!(a.len(), 9);
+        assert_eq!(a.union(&b).to_string(), "1-6,10-20");
+        assert_eq!(a.intersection(&b).to_string(), "2-5,11-12");
+        assert_eq!(a.difference(&b).to_string(), "1-1,6-6,10-10");
+        assert_eq!(b.difference(&a).to_string(), "13-20");
+        assert!(a.contains_range(&(2..=5)));
+        assert!(!a.contains_range(&(5..=10)));
+        assert!(!a.contains(8));
+    }
+
+    #[test]
+    fn test_coverage() {
+        let elves = Assignments::from_file("ex/day4.txt").unwrap().elves();
+        let coverage = Coverage::new(&elves);
+        assert_eq!(coverage.at_least(3).to_string(), "2-8");
+        assert_eq!(
+            coverage.redundant(&elves),
+            vec![0, 1, 2, 3, 4, 6, 7, 8, 9, 10, 11]
+        );
+        assert_eq!(overlapping_pairs(&elves).len(), 49);
+        assert_eq!(
+            overlapping_pairs(&[1..=2, 3..=4, 2..=3]),
+            vec![(0, 2), (1, 2)]
+        );
+    }
+}
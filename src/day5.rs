@@ -0,0 +1,810 @@
+use anyhow::{anyhow, bail};
+use std::{
+    collections::{HashSet, VecDeque},
+    fmt::Display,
+};
+
+use crate::{
+    input::Input,
+    parse::{Fields, LineError, LineResult},
+    Solution,
+};
+
+#[derive(Clone, Debug)]
+pub struct CratePuzzle {
+    name: String,
+    pub state: CrateState,
+    /// Each instruction with its line number.
+    pub script: Vec<(usize, CrateInstruction)>,
+}
+
+/// The stacks, bottom crate first, along with how they were drawn.
+#[derive(Clone, Debug)]
+pub struct CrateState {
+    stacks: Vec<VecDeque<char>>,
+    /// The line numbering the stacks, as drawn.
+    labels: String,
+    /// How many characters each crate row was drawn with, bottom row first,
+    /// so that trailing spaces come back as they were. Higher rows take the
+    /// width of the top one.
+    widths: Vec<usize>,
+}
+
+/// States are equal when they draw the same.
+impl PartialEq for CrateState {
+    fn eq(&self, other: &Self) -> bool {
+        self.stacks == other.stacks
+            && self.labels == other.labels
+            && (0..self.height()).all(|level| self.row(level) == other.row(level))
+    }
+}
+
+impl Eq for CrateState {}
+
+impl CrateState {
+    /// Stacks drawn with each label centred under its crates.
+    pub fn new(stacks: Vec<VecDeque<char>>) -> Self {
+        let labels = (1..=stacks.len())
+            .map(|i| format!("{i:^3}"))
+            .collect::<Vec<_>>()
+            .join(" ");
+        Self {
+            stacks,
+            labels,
+            widths: vec![],
+        }
+    }
+
+    pub fn stacks(&self) -> &[VecDeque<char>] {
+        &self.stacks
+    }
+
+    fn height(&self) -> usize {
+        self.stacks.iter().map(|s| s.len()).max().unwrap_or(0)
+    }
+
+    /// The crate row at `level`, counting from 0 at the bottom, as drawn.
+    fn row(&self, level: usize) -> String {
+        let cells = self
+            .stacks
+            .iter()
+            .map(|s| s.get(level).map_or("   ".to_owned(), |c| format!("[{c}]")))
+            .collect::<Vec<_>>();
+        let row = cells.join(" ");
+        let width = self
+            .widths
+            .get(level)
+            .or(self.widths.last())
+            .copied()
+            .unwrap_or(row.chars().count());
+        format!("{:<width$}", row.trim_end())
+    }
+
+    /// The top crate of each stack, skipping empty stacks.
+    pub fn tops(&self) -> String {
+        self.stacks.iter().filter_map(|s| s.back()).collect()
+    }
+
+    /// Fails if `instruction` names a missing stack or more crates than its
+    /// starting stack holds.
+    pub fn check(&self, instruction: &CrateInstruction) -> anyhow::Result<()> {
+        for stack in [instruction.start, instruction.end] {
+            if !(1..=self.stacks.len()).contains(&stack) {
+                bail!("there is no stack {stack}");
+            }
+        }
+        let height = self.stacks[instruction.start - 1].len();
+        if instruction.quantity > height {
+            bail!("stack {} holds only {height} crates", instruction.start);
+        }
+        Ok(())
+    }
+
+    /// Checks `instruction`, then has `crane` carry it out.
+    pub fn execute(
+        &mut self,
+        crane: &dyn Crane,
+        instruction: &CrateInstruction,
+    ) -> anyhow::Result<()> {
+        self.check(instruction)?;
+        crane.carry(instruction, self);
+        Ok(())
+    }
+
+    /// Moves the top `quantity` crates of `start` onto `end` in one lift,
+    /// keeping their order; stacks are numbered from 1.
+    fn lift(&mut self, quantity: usize, start: usize, end: usize) {
+        let from = &mut self.stacks[start - 1];
+        let crates = from.split_off(from.len() - quantity);
+        self.stacks[end - 1].extend(crates);
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct CrateInstruction {
+    pub quantity: usize,
+    pub start: usize,
+    pub end: usize,
+}
+
+impl CrateInstruction {
+    /// Moves `quantity` crates the opposite way.
+    pub fn reversed(&self, quantity: usize) -> Self {
+        Self {
+            quantity,
+            start: self.end,
+            end: self.start,
+        }
+    }
+}
+
+impl Display for CrateInstruction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "move {} from {} to {}",
+            self.quantity, self.start, self.end
+        )
+    }
+}
+
+/// How a crane carries out an instruction.
+pub trait Crane {
+    fn name(&self) -> String;
+
+    /// Moves the crates; `instruction` has already been checked against `state`.
+    fn carry(&self, instruction: &CrateInstruction, state: &mut CrateState);
+
+    /// Instructions that, carried out by this crane straight after
+    /// `instruction`, put every crate back where it was.
+    fn inverse(&self, instruction: &CrateInstruction) -> Vec<CrateInstruction> {
+        vec![instruction.reversed(instruction.quantity)]
+    }
+}
+
+/// Lifts one crate at a time.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct CrateMover9000;
+
+impl Crane for CrateMover9000 {
+    fn name(&self) -> String {
+        "CrateMover 9000".to_owned()
+    }
+
+    fn carry(&self, instruction: &CrateInstruction, state: &mut CrateState) {
+        for _ in 0..instruction.quantity {
+            state.lift(1, instruction.start, instruction.end);
+        }
+    }
+}
+
+/// Lifts every crate of an instruction at once.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct CrateMover9001;
+
+impl Crane for CrateMover9001 {
+    fn name(&self) -> String {
+        "CrateMover 9001".to_owned()
+    }
+
+    fn carry(&self, instruction: &CrateInstruction, state: &mut CrateState) {
+        state.lift(instruction.quantity, instruction.start, instruction.end);
+    }
+}
+
+/// Lifts at most `capacity` crates at once, splitting larger instructions
+/// into several lifts from the top down.
+#[derive(Copy, Clone, Debug)]
+pub struct LimitedCrane {
+    capacity: usize,
+}
+
+impl LimitedCrane {
+    pub fn new(capacity: usize) -> anyhow::Result<Self> {
+        if capacity == 0 {
+            bail!("A crane must lift at least one crate");
+        }
+        Ok(Self { capacity })
+    }
+}
+
+impl Crane for LimitedCrane {
+    fn name(&self) -> String {
+        format!("crane lifting {}", self.capacity)
+    }
+
+    fn carry(&self, instruction: &CrateInstruction, state: &mut CrateState) {
+        let mut remaining = instruction.quantity;
+        while remaining > 0 {
+            let lifted = remaining.min(self.capacity);
+            state.lift(lifted, instruction.start, instruction.end);
+            remaining -= lifted;
+        }
+    }
+
+    /// Lifts the last, smallest lift back first, then the full ones.
+    fn inverse(&self, instruction: &CrateInstruction) -> Vec<CrateInstruction> {
+        let full = instruction.quantity / self.capacity;
+        let rest = instruction.quantity % self.capacity;
+        let mut lifts = vec![self.capacity; full];
+        if rest > 0 {
+            lifts.insert(0, rest);
+        }
+        lifts.iter().map(|n| instruction.reversed(*n)).collect()
+    }
+}
+
+pub const CRANES: &str = "9000, 9001 or lift:N";
+
+/// The crane called `name`, one of `CRANES`.
+pub fn crane(name: &str) -> anyhow::Result<Box<dyn Crane>> {
+    match name {
+        "9000" => Ok(Box::new(CrateMover9000)),
+        "9001" => Ok(Box::new(CrateMover9001)),
+        _ => match name.strip_prefix("lift:").map(|n| n.parse::<usize>()) {
+            Some(Ok(capacity)) => Ok(Box::new(LimitedCrane::new(capacity)?)),
+            _ => bail!("{name}: Unknown crane; expected {CRANES}"),
+        },
+    }
+}
+
+impl Display for CrateState {
+    /// Draws the stacks as they were parsed, so that an unchanged state
+    /// reproduces its drawing exactly.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for level in (0..self.height()).rev() {
+            writeln!(f, "{}", self.row(level))?;
+        }
+        writeln!(f, "{}", self.labels)
+    }
+}
+
+/// The cells of a crate row, ignoring trailing spaces, which may pad a row
+/// to any width.
+fn decode_row(line: &str) -> LineResult<Vec<Option<char>>> {
+    let chars = line.trim_end_matches(' ').chars().collect::<Vec<_>>();
+    let mut row = vec![];
+    for (i, cell) in chars.chunks(4).enumerate() {
+        let column = i * 4 + 1;
+        match cell {
+            ['[', c, ']'] | ['[', c, ']', ' '] if !c.is_whitespace() => row.push(Some(*c)),
+            [' ', ' ', ' '] | [' ', ' ', ' ', ' '] => row.push(None),
+            _ => {
+                let found = cell.iter().collect::<String>();
+                return Err(LineError::new(
+                    column,
+                    "a crate like [A] or spaces",
+                    found.as_str(),
+                ));
+            }
+        }
+    }
+    Ok(row)
+}
+
+/// A crate in `above` with no crate under it in `below`.
+fn floating_crate(above: &[Option<char>], below: &[Option<char>]) -> Option<LineError> {
+    above.iter().enumerate().find_map(|(i, cell)| {
+        let c = (*cell)?;
+        if below.get(i).copied().flatten().is_some() {
+            None
+        } else {
+            let found = format!("[{c}] over an empty space");
+            Some(LineError::new(
+                i * 4 + 1,
+                "a crate resting on another",
+                found.as_str(),
+            ))
+        }
+    })
+}
+
+impl CrateInstruction {
+    pub fn from_line(line: &str, num_stacks: usize) -> LineResult<Self> {
+        let mut fields = Fields::new(line);
+        fields.expect("move")?;
+        let quantity = fields.parse("a crate count")?;
+        fields.expect("from")?;
+        let start = stack_number(&mut fields, num_stacks)?;
+        fields.expect("to")?;
+        let end = stack_number(&mut fields, num_stacks)?;
+        fields.end()?;
+        Ok(Self {
+            quantity,
+            start,
+            end,
+        })
+    }
+}
+
+/// Builds the stacks from their crate rows, given as each line's length and
+/// cells, and the line labelling them. Each label must lie under the middle
+/// of its stack's crates.
+fn stack_drawing(rows: &[(usize, Vec<Option<char>>)], labels: &str) -> LineResult<CrateState> {
+    let mut fields = Fields::new(labels);
+    let mut num_stacks = 0;
+    while let Ok((column, field)) = fields.next("a stack number") {
+        let expected = format!("stack number {}", num_stacks + 1);
+        let middle = num_stacks * 4 + 2;
+        if field != (num_stacks + 1).to_string()
+            || column > middle
+            || column + field.len() <= middle
+        {
+            return Err(LineError::new(column, expected.as_str(), field));
+        }
+        num_stacks += 1;
+    }
+    let width = rows.iter().map(|(_, row)| row.len()).max().unwrap_or(0);
+    if width > num_stacks {
+        let expected = format!("stack number {}", num_stacks + 1);
+        return Err(LineError::new(
+            fields.end_column(),
+            expected.as_str(),
+            "end of line",
+        ));
+    }
+    let mut stacks = vec![VecDeque::new(); num_stacks];
+    for (_, row) in rows.iter().rev() {
+        for (i, c) in row.iter().enumerate() {
+            if let Some(c) = c {
+                stacks[i].push_back(*c);
+            }
+        }
+    }
+    Ok(CrateState {
+        stacks,
+        labels: labels.to_owned(),
+        widths: rows.iter().rev().map(|(length, _)| *length).collect(),
+    })
+}
+
+fn stack_number(fields: &mut Fields, num_stacks: usize) -> LineResult<usize> {
+    let expected = format!("a stack number from 1 to {num_stacks}");
+    let (column, field) = fields.next(expected.as_str())?;
+    match field.parse::<usize>() {
+        Ok(n) if (1..=num_stacks).contains(&n) => Ok(n),
+        _ => Err(LineError::new(column, expected.as_str(), field)),
+    }
+}
+
+impl CratePuzzle {
+    pub fn from_file(filename: &str) -> anyhow::Result<Self> {
+        Self::from_input(&Input::from_file(filename)?)
+    }
+
+    pub fn from_input(input: &Input) -> anyhow::Result<Self> {
+        let mut rows: Vec<(usize, Vec<Option<char>>)> = vec![];
+        let mut state = None;
+        let mut script = vec![];
+        for (i, line) in input.lines().enumerate() {
+            let line_number = i + 1;
+            let located = |e: LineError| e.at(input.name(), line_number);
+            match &state {
+                Some(CrateState { stacks, .. }) => {
+                    if !line.trim().is_empty() {
+                        let instruction =
+                            CrateInstruction::from_line(line, stacks.len()).map_err(located)?;
+                        script.push((line_number, instruction));
+                    }
+                }
+                None if line.trim_start().starts_with(|c: char| c.is_ascii_digit()) => {
+                    state = Some(stack_drawing(&rows, line).map_err(located)?);
+                }
+                None => {
+                    let row = decode_row(line).map_err(located)?;
+                    if let Some((_, above)) = rows.last() {
+                        if let Some(e) = floating_crate(above, &row) {
+                            return Err(e.at(input.name(), line_number - 1).into());
+                        }
+                    }
+                    rows.push((line.chars().count(), row));
+                }
+            }
+        }
+        match state {
+            Some(state) => Ok(Self {
+                name: input.name().to_owned(),
+                state,
+                script,
+            }),
+            None => bail!("{}: No line numbering the stacks", input.name()),
+        }
+    }
+
+    /// A puzzle numbering its instructions by where its `Display` puts them.
+    pub fn new(name: &str, state: CrateState, instructions: Vec<CrateInstruction>) -> Self {
+        let height = state.height();
+        let script = instructions
+            .into_iter()
+            .enumerate()
+            .map(|(i, instruction)| (height + 3 + i, instruction))
+            .collect();
+        Self {
+            name: name.to_owned(),
+            state,
+            script,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    /// The stacks after `crane` carries out the whole script, or the first
+    /// instruction that cannot be carried out.
+    pub fn run(&self, crane: &dyn Crane) -> anyhow::Result<CrateState> {
+        let mut rearrangement = Rearrangement::new(self, crane);
+        rearrangement.seek(self.script.len())?;
+        Ok(rearrangement.state)
+    }
+
+    /// A puzzle starting from where `crane` leaves the stacks, whose script
+    /// puts them back as they started.
+    pub fn inverse(&self, crane: &dyn Crane) -> anyhow::Result<Self> {
+        let instructions = self
+            .script
+            .iter()
+            .rev()
+            .flat_map(|(_, instruction)| crane.inverse(instruction))
+            .collect();
+        let name = format!("inverse of {}", self.name);
+        Ok(Self::new(name.as_str(), self.run(crane)?, instructions))
+    }
+}
+
+impl Display for CratePuzzle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{}", self.state)?;
+        for (_, instruction) in self.script.iter() {
+            writeln!(f, "{instruction}")?;
+        }
+        Ok(())
+    }
+}
+
+/// A puzzle's script part way through, which can be stepped forward or
+/// undone one instruction at a time.
+pub struct Rearrangement<'a> {
+    puzzle: &'a CratePuzzle,
+    crane: &'a dyn Crane,
+    state: CrateState,
+    done: usize,
+}
+
+impl<'a> Rearrangement<'a> {
+    pub fn new(puzzle: &'a CratePuzzle, crane: &'a dyn Crane) -> Self {
+        Self {
+            puzzle,
+            crane,
+            state: puzzle.state.clone(),
+            done: 0,
+        }
+    }
+
+    pub fn state(&self) -> &CrateState {
+        &self.state
+    }
+
+    /// How many instructions have been carried out.
+    pub fn done(&self) -> usize {
+        self.done
+    }
+
+    /// Carries out the next instruction, returning false if there are none left.
+    pub fn forward(&mut self) -> anyhow::Result<bool> {
+        let Some((line, instruction)) = self.puzzle.script.get(self.done) else {
+            return Ok(false);
+        };
+        self.state
+            .execute(self.crane, instruction)
+            .map_err(|e| anyhow!("{}:{line}: {instruction}: {e}", self.puzzle.name))?;
+        self.done += 1;
+        Ok(true)
+    }
+
+    /// Undoes the last instruction carried out, returning false if there are none.
+    pub fn back(&mut self) -> bool {
+        if self.done == 0 {
+            return false;
+        }
+        self.done -= 1;
+        let (_, instruction) = self.puzzle.script[self.done];
+        for undo in self.crane.inverse(&instruction) {
+            self.crane.carry(&undo, &mut self.state);
+        }
+        true
+    }
+
+    /// Steps forward or back to the state after `done` instructions.
+    pub fn seek(&mut self, done: usize) -> anyhow::Result<&CrateState> {
+        if done > self.puzzle.script.len() {
+            bail!(
+                "{}: The script has only {} instructions",
+                self.puzzle.name,
+                self.puzzle.script.len()
+            );
+        }
+        while self.done > done {
+            self.back();
+        }
+        while self.done < done {
+            self.forward()?;
+        }
+        Ok(&self.state)
+    }
+}
+
+impl Solution for CratePuzzle {
+    type Part1 = String;
+    type Part2 = String;
+
+    fn load(input: &Input) -> anyhow::Result<Self> {
+        Self::from_input(input)
+    }
+
+    fn part1(&self) -> anyhow::Result<String> {
+        Ok(self.run(&CrateMover9000)?.tops())
+    }
+
+    fn part2(&self) -> anyhow::Result<String> {
+        Ok(self.run(&CrateMover9001)?.tops())
+    }
+}
+
+/// What a planned script should leave the stacks looking like.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Goal {
+    /// Every stack holding exactly these crates.
+    Arrangement(CrateState),
+    /// These crates on top, as `CrateState::tops` lists them.
+    Tops(String),
+}
+
+impl Goal {
+    pub fn reached(&self, state: &CrateState) -> bool {
+        match self {
+            Self::Arrangement(goal) => goal.stacks == state.stacks,
+            Self::Tops(tops) => state.tops() == *tops,
+        }
+    }
+
+    /// Fails if no script could reach the goal from `state`, because the
+    /// crates it needs are not there.
+    fn check(&self, state: &CrateState) -> anyhow::Result<()> {
+        let mut crates = state.stacks.iter().flatten().copied().collect::<Vec<_>>();
+        let mut needed = match self {
+            Self::Arrangement(goal) => {
+                if goal.stacks.len() != state.stacks.len() {
+                    bail!(
+                        "The goal has {} stacks, not {}",
+                        goal.stacks.len(),
+                        state.stacks.len()
+                    );
+                }
+                goal.stacks.iter().flatten().copied().collect::<Vec<_>>()
+            }
+            Self::Tops(tops) => tops.chars().collect(),
+        };
+        crates.sort_unstable();
+        needed.sort_unstable();
+        let mut available = crates.iter().peekable();
+        for c in needed.iter() {
+            while available.next_if(|a| *a < c).is_some() {}
+            if available.next_if(|a| *a == c).is_none() {
+                bail!("There are not enough {c} crates for the goal");
+            }
+        }
+        if matches!(self, Self::Arrangement(_)) && available.next().is_some() {
+            bail!("The goal leaves out some crates");
+        }
+        Ok(())
+    }
+}
+
+/// Searches breadth first for the shortest script reaching a goal.
+#[derive(Copy, Clone, Debug)]
+pub struct Planner {
+    /// Gives up after visiting this many arrangements.
+    pub max_states: usize,
+}
+
+impl Default for Planner {
+    fn default() -> Self {
+        Self {
+            max_states: 1_000_000,
+        }
+    }
+}
+
+impl Planner {
+    pub fn plan(
+        &self,
+        start: &CrateState,
+        goal: &Goal,
+        crane: &dyn Crane,
+    ) -> anyhow::Result<Vec<CrateInstruction>> {
+        goal.check(start)?;
+        if goal.reached(start) {
+            return Ok(vec![]);
+        }
+        let mut visited = HashSet::from([start.stacks.clone()]);
+        let mut states: Vec<(CrateState, Option<(usize, CrateInstruction)>)> =
+            vec![(start.clone(), None)];
+        let mut queue = VecDeque::from([0]);
+        while let Some(i) = queue.pop_front() {
+            for instruction in Self::moves(&states[i].0) {
+                let mut next = states[i].0.clone();
+                crane.carry(&instruction, &mut next);
+                if visited.insert(next.stacks.clone()) {
+                    if states.len() == self.max_states {
+                        bail!("No script found within {} arrangements", self.max_states);
+                    }
+                    let reached = goal.reached(&next);
+                    queue.push_back(states.len());
+                    states.push((next, Some((i, instruction))));
+                    if reached {
+                        return Ok(Self::script_to(&states, states.len() - 1));
+                    }
+                }
+            }
+        }
+        bail!("No script reaches the goal")
+    }
+
+    /// Every instruction that moves at least one crate.
+    fn moves(state: &CrateState) -> Vec<CrateInstruction> {
+        let mut moves = vec![];
+        for (start, stack) in state.stacks.iter().enumerate() {
+            for end in (0..state.stacks.len()).filter(|end| *end != start) {
+                for quantity in 1..=stack.len() {
+                    moves.push(CrateInstruction {
+                        quantity,
+                        start: start + 1,
+                        end: end + 1,
+                    });
+                }
+            }
+        }
+        moves
+    }
+
+    fn script_to(
+        states: &[(CrateState, Option<(usize, CrateInstruction)>)],
+        mut i: usize,
+    ) -> Vec<CrateInstruction> {
+        let mut script = vec![];
+        while let Some((parent, instruction)) = states[i].1 {
+            script.push(instruction);
+            i = parent;
+        }
+        script.reverse();
+        script
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::VecDeque;
+
+    use crate::{
+        day5::{crane, CratePuzzle, CrateState, Goal, Planner, Rearrangement},
+        input::Input,
+    };
+
+    #[test]
+    fn test_drawing() {
+        let input = Input::from_file("ex/day5.txt").unwrap();
+        let puzzle = CratePuzzle::from_input(&input).unwrap();
+        assert!(input.text().starts_with(puzzle.state.to_string().as_str()));
+
+        let drawing = "                                        [ü]
+[a] [*]                             [1] [Z] [-]
+ 1   2   3   4   5   6   7   8   9  10  11  12
+";
+        let input = format!("{drawing}\nmove 1 from 11 to 12\n");
+        let puzzle = CratePuzzle::from_input(&Input::from(input.as_str())).unwrap();
+        assert_eq!(puzzle.state.stacks().len(), 12);
+        assert_eq!(puzzle.state.stacks()[10], VecDeque::from(['Z', 'ü']));
+        assert_eq!(puzzle.state.to_string(), drawing);
+        assert_eq!(puzzle.script[0].1.start, 11);
+
+        let state = CrateState::new(vec![VecDeque::from(['A']); 10]);
+        assert!(state.to_string().ends_with(" 9  10 \n"));
+        let bad = "[A] [B]\n 1\n\nmove 1 from 1 to 1\n";
+        assert!(CratePuzzle::from_input(&Input::from(bad)).is_err());
+
+        let mixed = "    [C]\n[A] [B]    \n[D] [E]   \n 1   2   3\n";
+        let input = format!("{mixed}\nmove 1 from 2 to 3\n");
+        let puzzle = CratePuzzle::from_input(&Input::from(input.as_str())).unwrap();
+        assert_eq!(puzzle.state.to_string(), mixed);
+        assert_eq!(puzzle.to_string(), input);
+    }
+
+    #[test]
+    fn test_floating_crate() {
+        let floating = "    [C]\n[A]\n[D] [E]\n 1   2\n\nmove 1 from 1 to 2\n";
+        let error = CratePuzzle::from_input(&Input::new("crates", floating)).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "crates:1:5: expected a crate resting on another, found \"[C] over an empty space\""
+        );
+    }
+
+    #[test]
+    fn test_cranes() {
+        let puzzle = CratePuzzle::from_file("ex/day5.txt").unwrap();
+        let tops = |name| puzzle.run(crane(name).unwrap().as_ref()).unwrap().tops();
+        assert_eq!(tops("9000"), "CMZ");
+        assert_eq!(tops("9001"), "MCD");
+        assert_eq!(tops("lift:2"), "MCZ");
+        assert_eq!(tops("lift:3"), "MCD");
+        assert!(crane("lift:0").is_err());
+        assert!(crane("9002").is_err());
+
+        let text = Input::from_file("ex/day5.txt").unwrap().text().to_owned();
+        let text = text.replace("move 2 from 2 to 1", "move 3 from 2 to 1");
+        let puzzle = CratePuzzle::from_input(&Input::new("crates", text.as_str())).unwrap();
+        let error = puzzle.run(crane("9000").unwrap().as_ref()).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "crates:8: move 3 from 2 to 1: stack 2 holds only 2 crates"
+        );
+    }
+
+    #[test]
+    fn test_undo() {
+        let puzzle = CratePuzzle::from_file("ex/day5.txt").unwrap();
+        for name in ["9000", "9001", "lift:2"] {
+            let crane = crane(name).unwrap();
+            let mut rearrangement = Rearrangement::new(&puzzle, crane.as_ref());
+            let mut states = vec![rearrangement.state().clone()];
+            while rearrangement.forward().unwrap() {
+                states.push(rearrangement.state().clone());
+            }
+            assert_eq!(rearrangement.seek(1).unwrap(), &states[1]);
+            assert!(rearrangement.back());
+            assert_eq!(rearrangement.state(), &states[0]);
+            assert!(!rearrangement.back());
+            assert!(rearrangement.seek(5).is_err());
+
+            let inverse = puzzle.inverse(crane.as_ref()).unwrap();
+            let text = inverse.to_string();
+            let reparsed = CratePuzzle::from_input(&Input::new("inverse", text.as_str())).unwrap();
+            assert_eq!(reparsed.script, inverse.script, "{name}");
+            assert_eq!(
+                reparsed.run(crane.as_ref()).unwrap(),
+                puzzle.state,
+                "{name}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_plan() {
+        let planner = Planner::default();
+        let start = CrateState::new(vec![VecDeque::from(['A', 'B']), VecDeque::new()]);
+        let goal = Goal::Arrangement(CrateState::new(vec![
+            VecDeque::new(),
+            VecDeque::from(['B', 'A']),
+        ]));
+        for (name, moves) in [("9000", 1), ("9001", 2), ("lift:1", 1)] {
+            let crane = crane(name).unwrap();
+            let script = planner.plan(&start, &goal, crane.as_ref()).unwrap();
+            assert_eq!(script.len(), moves, "{name}");
+            let puzzle = CratePuzzle::new("plan", start.clone(), script);
+            assert!(goal.reached(&puzzle.run(crane.as_ref()).unwrap()));
+        }
+
+        let puzzle = CratePuzzle::from_file("ex/day5.txt").unwrap();
+        let crane = crane("9001").unwrap();
+        let goal = Goal::Tops("MCD".to_owned());
+        let script = planner.plan(&puzzle.state, &goal, crane.as_ref()).unwrap();
+        assert_eq!(script.len(), 3);
+        assert!(planner
+            .plan(&puzzle.state, &Goal::Tops("ZZ".to_owned()), crane.as_ref())
+            .is_err());
+        let small = Planner { max_states: 10 };
+        let goal = Goal::Arrangement(puzzle.run(crane.as_ref()).unwrap());
+        assert!(small.plan(&puzzle.state, &goal, crane.as_ref()).is_err());
+    }
+}
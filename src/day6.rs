@@ -0,0 +1,220 @@
+use std::collections::VecDeque;
+
+use anyhow::anyhow;
+
+use crate::{input::Input, parse::first_line, Solution};
+
+const START_OF_PACKET: usize = 4;
+const START_OF_MESSAGE: usize = 14;
+
+#[derive(Clone, Debug)]
+pub struct Datastream {
+    line: String,
+}
+
+impl Datastream {
+    pub fn from_file(filename: &str) -> anyhow::Result<Self> {
+        Self::from_input(&Input::from_file(filename)?)
+    }
+
+    pub fn from_input(input: &Input) -> anyhow::Result<Self> {
+        Ok(Self {
+            line: first_line(input, "a datastream")?.to_owned(),
+        })
+    }
+
+    fn marker(&self, length: usize) -> anyhow::Result<usize> {
+        scanner(self.line.as_str(), length)
+            .ok_or_else(|| anyhow!("No {length} different characters in a row"))
+    }
+}
+
+impl Solution for Datastream {
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn load(input: &Input) -> anyhow::Result<Self> {
+        Self::from_input(input)
+    }
+
+    fn part1(&self) -> anyhow::Result<usize> {
+        self.marker(START_OF_PACKET)
+    }
+
+    fn part2(&self) -> anyhow::Result<usize> {
+        self.marker(START_OF_MESSAGE)
+    }
+}
+
+/// The number of bytes read when the last `target_len` bytes of `line` are
+/// first all different. Panics if `target_len` is 0.
+pub fn scanner(line: &str, target_len: usize) -> Option<usize> {
+    first_markers(line.bytes(), &[target_len])[0]
+}
+
+/// The end of a run of `length` different bytes, counting bytes from 1.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Marker {
+    pub length: usize,
+    pub position: usize,
+}
+
+/// Every marker in `bytes` for each of `lengths`, in order of position.
+/// Panics if any length is 0.
+pub fn markers<I: IntoIterator<Item = u8>>(bytes: I, lengths: &[usize]) -> Vec<Marker> {
+    let mut scanner = MarkerScanner::new(lengths);
+    let mut markers = vec![];
+    for byte in bytes {
+        scanner.push(byte);
+        markers.extend(scanner.markers().map(|length| Marker {
+            length,
+            position: scanner.position(),
+        }));
+    }
+    markers
+}
+
+/// The first marker position for each of `lengths`, reading no further
+/// than needed to find them all. Panics if any length is 0.
+pub fn first_markers<I: IntoIterator<Item = u8>>(
+    bytes: I,
+    lengths: &[usize],
+) -> Vec<Option<usize>> {
+    let mut scanner = MarkerScanner::new(lengths);
+    let mut found = vec![None; lengths.len()];
+    for byte in bytes {
+        scanner.push(byte);
+        for length in scanner.markers() {
+            for (i, _) in lengths.iter().enumerate().filter(|(_, l)| **l == length) {
+                found[i].get_or_insert(scanner.position());
+            }
+        }
+        if found.iter().all(|f| f.is_some()) {
+            break;
+        }
+    }
+    found
+}
+
+/// Tracks how many times each byte occurs in the latest window of each
+/// length, so that each byte read takes constant time per window.
+#[derive(Clone, Debug)]
+pub struct MarkerScanner {
+    windows: Vec<Window>,
+    recent: VecDeque<u8>,
+    longest: usize,
+    position: usize,
+}
+
+#[derive(Clone, Debug)]
+struct Window {
+    length: usize,
+    counts: [usize; 256],
+    distinct: usize,
+}
+
+impl MarkerScanner {
+    /// Panics if any length is 0, as every position would be a marker.
+    pub fn new(lengths: &[usize]) -> Self {
+        assert!(
+            lengths.iter().all(|length| *length > 0),
+            "Marker lengths must be at least 1"
+        );
+        let windows = lengths
+            .iter()
+            .map(|length| Window {
+                length: *length,
+                counts: [0; 256],
+                distinct: 0,
+            })
+            .collect();
+        let longest = lengths.iter().copied().max().unwrap_or(0);
+        Self {
+            windows,
+            recent: VecDeque::with_capacity(longest + 1),
+            longest,
+            position: 0,
+        }
+    }
+
+    /// How many bytes have been read.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    pub fn push(&mut self, byte: u8) {
+        self.position += 1;
+        self.recent.push_back(byte);
+        for window in self.windows.iter_mut() {
+            window.counts[byte as usize] += 1;
+            if window.counts[byte as usize] == 1 {
+                window.distinct += 1;
+            }
+            if self.recent.len() > window.length {
+                let old = self.recent[self.recent.len() - 1 - window.length] as usize;
+                window.counts[old] -= 1;
+                if window.counts[old] == 0 {
+                    window.distinct -= 1;
+                }
+            }
+        }
+        if self.recent.len() > self.longest {
+            self.recent.pop_front();
+        }
+    }
+
+    /// The lengths of the windows whose bytes are all different after the
+    /// latest byte.
+    pub fn markers(&self) -> impl Iterator<Item = usize> + '_ {
+        self.windows
+            .iter()
+            .filter(|w| self.position >= w.length && w.distinct == w.length)
+            .map(|w| w.length)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::day6::{first_markers, markers, scanner, Marker};
+
+    #[test]
+    fn test_markers() {
+        let line = "mjqjpqmgbljsphdztnvjfqwrcgsmlb";
+        assert_eq!(scanner(line, 4), Some(7));
+        assert_eq!(scanner(line, 14), Some(19));
+        assert_eq!(scanner("aaaa", 2), None);
+        assert_eq!(
+            first_markers(line.bytes(), &[14, 4, 40]),
+            vec![Some(19), Some(7), None]
+        );
+        let all = markers("abcabba".bytes(), &[2, 3]);
+        let positions = |length| {
+            all.iter()
+                .filter(|m| m.length == length)
+                .map(|m| m.position)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(positions(2), vec![2, 3, 4, 5, 7]);
+        assert_eq!(positions(3), vec![3, 4, 5]);
+        assert_eq!(
+            all[..2],
+            [
+                Marker {
+                    length: 2,
+                    position: 2
+                },
+                Marker {
+                    length: 2,
+                    position: 3
+                }
+            ]
+        );
+        assert_eq!(markers([0, 255, 0, 255], &[2]).len(), 3);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_empty_window() {
+        markers("abc".bytes(), &[4, 0]);
+    }
+}
@@ -0,0 +1,151 @@
+use std::collections::BTreeMap;
+
+use crate::{
+    input::Input,
+    parse::{parse_lines, Fields, LineError},
+    Solution,
+};
+
+#[derive(Debug, Clone)]
+pub enum FileEntry {
+    File(usize),
+    Directory(Option<usize>, BTreeMap<String, usize>),
+}
+
+impl FileEntry {
+    pub fn is_directory(&self) -> bool {
+        match self {
+            FileEntry::File(_) => false,
+            FileEntry::Directory(_, _) => true,
+        }
+    }
+
+    pub fn size(&self, system: &FileSystem) -> usize {
+        match self {
+            FileEntry::File(size) => *size,
+            FileEntry::Directory(_, children) => children
+                .values()
+                .map(|i| system.inode2object[*i].size(system))
+                .sum(),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct FileSystem {
+    inode2object: Vec<FileEntry>,
+}
+
+impl FileSystem {
+    pub fn from_file(filename: &str) -> anyhow::Result<Self> {
+        Self::from_input(&Input::from_file(filename)?)
+    }
+
+    pub fn from_input(input: &Input) -> anyhow::Result<Self> {
+        let mut system = FileSystem::default();
+        let mut current_dir = system.new_directory(None);
+        parse_lines(input, |line| {
+            let mut fields = Fields::new(line);
+            let (column, first) = fields.next("a command or directory entry")?;
+            if first == "$" {
+                let (column, command) = fields.next("cd or ls")?;
+                match command {
+                    "cd" => {
+                        let (column, dir_name) = fields.next("a directory name")?;
+                        current_dir = match dir_name {
+                            ".." => match system.inode2object[current_dir] {
+                                FileEntry::Directory(Some(parent), _) => parent,
+                                _ => current_dir,
+                            },
+                            "/" => 0,
+                            _ => match &system.inode2object[current_dir] {
+                                FileEntry::Directory(_, children) => children
+                                    .get(dir_name)
+                                    .copied()
+                                    .filter(|id| system.inode2object[*id].is_directory())
+                                    .ok_or_else(|| {
+                                        LineError::new(column, "a listed directory", dir_name)
+                                    })?,
+                                FileEntry::File(_) => current_dir,
+                            },
+                        };
+                    }
+                    "ls" => {}
+                    _ => return Err(LineError::new(column, "cd or ls", command)),
+                }
+            } else {
+                let id = if first == "dir" {
+                    system.new_directory(Some(current_dir))
+                } else {
+                    let size = first
+                        .parse::<usize>()
+                        .map_err(|_| LineError::new(column, "\"dir\" or a file size", first))?;
+                    system.new_file(size)
+                };
+                let (_, name) = fields.next("a name")?;
+                if let FileEntry::Directory(_, children) = &mut system.inode2object[current_dir] {
+                    children.insert(name.to_owned(), id);
+                }
+            }
+            fields.end()
+        })?;
+        Ok(system)
+    }
+
+    fn new_directory(&mut self, parent: Option<usize>) -> usize {
+        let id = self.inode2object.len();
+        self.inode2object
+            .push(FileEntry::Directory(parent, BTreeMap::new()));
+        id
+    }
+
+    fn new_file(&mut self, size: usize) -> usize {
+        let id = self.inode2object.len();
+        self.inode2object.push(FileEntry::File(size));
+        id
+    }
+
+    pub fn directory_sizes(&self) -> Vec<usize> {
+        self.inode2object
+            .iter()
+            .filter(|entry| entry.is_directory())
+            .map(|dir| dir.size(self))
+            .collect()
+    }
+
+    pub fn small_directory_total(&self) -> usize {
+        let sizes = self.directory_sizes();
+        sizes.iter().filter(|s| **s <= 100000).sum()
+    }
+
+    pub fn directory_to_delete(&self) -> usize {
+        const TOTAL_SPACE: usize = 70000000;
+        const SPACE_NEEDED: usize = 30000000;
+        let sizes = self.directory_sizes();
+        let space_available = TOTAL_SPACE - sizes[0];
+        sizes
+            .iter()
+            .skip(1)
+            .filter(|s| space_available + *s >= SPACE_NEEDED)
+            .min()
+            .copied()
+            .unwrap()
+    }
+}
+
+impl Solution for FileSystem {
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn load(input: &Input) -> anyhow::Result<Self> {
+        Self::from_input(input)
+    }
+
+    fn part1(&self) -> anyhow::Result<usize> {
+        Ok(self.small_directory_total())
+    }
+
+    fn part2(&self) -> anyhow::Result<usize> {
+        Ok(self.directory_to_delete())
+    }
+}
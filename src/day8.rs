@@ -0,0 +1,83 @@
+use advent_code_lib::{DirType, GridDigitWorld, ManhattanDir, Position};
+use enum_iterator::*;
+
+use crate::{input::Input, parse::check_grid, Solution};
+
+pub struct Forest {
+    map: GridDigitWorld,
+}
+
+impl Forest {
+    pub fn from_file(filename: &str) -> anyhow::Result<Self> {
+        Self::from_input(&Input::from_file(filename)?)
+    }
+
+    pub fn from_input(input: &Input) -> anyhow::Result<Self> {
+        check_grid(input, "a tree height digit", |c| c.is_ascii_digit())?;
+        Ok(Self {
+            map: input.text().parse::<GridDigitWorld>()?,
+        })
+    }
+}
+
+impl Solution for Forest {
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn load(input: &Input) -> anyhow::Result<Self> {
+        Self::from_input(input)
+    }
+
+    fn part1(&self) -> anyhow::Result<usize> {
+        Ok(part1(&self.map))
+    }
+
+    fn part2(&self) -> anyhow::Result<usize> {
+        Ok(part2(&self.map))
+    }
+}
+
+pub fn part1(map: &GridDigitWorld) -> usize {
+    map.position_iter()
+        .filter(|p| all::<ManhattanDir>().any(|dir| find_blocking_tree(map, *p, dir).is_none()))
+        .count()
+}
+
+pub fn find_blocking_tree(
+    map: &GridDigitWorld,
+    p: Position,
+    dir: ManhattanDir,
+) -> Option<Position> {
+    let tree_height = map.value(p).unwrap();
+    let mut p = dir.next_position(p);
+    while let Some(blocker) = map.value(p) {
+        if blocker >= tree_height {
+            return Some(p);
+        }
+        p = dir.next_position(p);
+    }
+    None
+}
+
+pub fn part2(map: &GridDigitWorld) -> usize {
+    map.position_iter()
+        .map(|p| scenic_score(map, p))
+        .max()
+        .unwrap()
+}
+
+pub fn scenic_score(map: &GridDigitWorld, p: Position) -> usize {
+    all::<ManhattanDir>()
+        .map(|dir| {
+            find_blocking_tree(map, p, dir).map_or_else(
+                || match dir {
+                    ManhattanDir::N => p.row as usize,
+                    ManhattanDir::W => p.col as usize,
+                    ManhattanDir::S => map.height() - 1 - p.row as usize,
+                    ManhattanDir::E => map.width() - 1 - p.col as usize,
+                },
+                |b| p.manhattan_distance(b),
+            )
+        })
+        .product()
+}
@@ -0,0 +1,158 @@
+use std::{
+    cmp::{max, min},
+    collections::BTreeSet,
+    iter::repeat_n,
+};
+
+use advent_code_lib::{Dir, Position};
+
+use crate::{
+    input::Input,
+    parse::{parse_lines, Fields, LineError, LineResult},
+    simulation::Simulation,
+    Solution,
+};
+
+#[derive(Clone)]
+pub struct RopeMotions {
+    motions: Vec<(Dir, usize)>,
+}
+
+impl RopeMotions {
+    pub fn from_file(filename: &str) -> anyhow::Result<Self> {
+        Self::from_input(&Input::from_file(filename)?)
+    }
+
+    pub fn from_input(input: &Input) -> anyhow::Result<Self> {
+        Ok(Self {
+            motions: parse_lines(input, parse_line)?,
+        })
+    }
+
+    pub fn tail_visit_count<const N: usize>(&self) -> usize {
+        let mut rope = Rope::new(self, N);
+        while rope.step() {}
+        rope.tail_visits.len()
+    }
+}
+
+/// A rope part way through its motions, one step of its head at a time.
+#[derive(Clone, Debug)]
+pub struct Rope {
+    steps: Vec<Dir>,
+    next_step: usize,
+    knots: Vec<Position>,
+    tail_visits: BTreeSet<Position>,
+}
+
+impl Rope {
+    pub fn new(motions: &RopeMotions, num_knots: usize) -> Self {
+        Self {
+            steps: motions
+                .motions
+                .iter()
+                .flat_map(|(dir, reps)| repeat_n(*dir, *reps))
+                .collect(),
+            next_step: 0,
+            knots: vec![Position::new(); num_knots],
+            tail_visits: BTreeSet::new(),
+        }
+    }
+}
+
+impl Simulation for Rope {
+    fn step(&mut self) -> bool {
+        match self.steps.get(self.next_step) {
+            None => false,
+            Some(dir) => {
+                self.next_step += 1;
+                self.knots[0].update(*dir);
+                for i in 1..self.knots.len() {
+                    move_if_needed(self.knots[i - 1], &mut self.knots[i]);
+                }
+                self.tail_visits.insert(*self.knots.last().unwrap());
+                true
+            }
+        }
+    }
+
+    /// Draws the head as H and the other knots by number, over # for each
+    /// position the tail has visited.
+    fn snapshot(&self) -> String {
+        let all = self.knots.iter().chain(self.tail_visits.iter());
+        let (mut min_col, mut max_col, mut min_row, mut max_row) = (0, 0, 0, 0);
+        for p in all {
+            min_col = min(min_col, p.col);
+            max_col = max(max_col, p.col);
+            min_row = min(min_row, p.row);
+            max_row = max(max_row, p.row);
+        }
+        let mut result = String::new();
+        for row in min_row..=max_row {
+            for col in min_col..=max_col {
+                let p = Position { col, row };
+                let c = match self.knots.iter().position(|k| *k == p) {
+                    Some(0) => 'H',
+                    Some(i) => char::from_digit(i as u32 % 10, 10).unwrap(),
+                    None if self.tail_visits.contains(&p) => '#',
+                    None if p == Position::new() => 's',
+                    None => '.',
+                };
+                result.push(c);
+            }
+            result.push('\n');
+        }
+        result
+    }
+}
+
+impl Solution for RopeMotions {
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn load(input: &Input) -> anyhow::Result<Self> {
+        Self::from_input(input)
+    }
+
+    fn part1(&self) -> anyhow::Result<usize> {
+        Ok(self.tail_visit_count::<2>())
+    }
+
+    fn part2(&self) -> anyhow::Result<usize> {
+        Ok(self.tail_visit_count::<10>())
+    }
+
+    fn simulation(&self) -> Option<Box<dyn Simulation>> {
+        Some(Box::new(Rope::new(self, 10)))
+    }
+}
+
+pub fn parse_line(line: &str) -> LineResult<(Dir, usize)> {
+    let mut fields = Fields::new(line);
+    let (column, dir) = fields.next("R, U, L or D")?;
+    let dir = match dir {
+        "R" => Dir::E,
+        "U" => Dir::N,
+        "L" => Dir::W,
+        "D" => Dir::S,
+        _ => return Err(LineError::new(column, "R, U, L or D", dir)),
+    };
+    let reps = fields.parse("a step count")?;
+    fields.end()?;
+    Ok((dir, reps))
+}
+
+pub fn move_if_needed(target: Position, p: &mut Position) {
+    if target.neighbors().all(|n| n != *p) {
+        if p.row < target.row {
+            p.row += 1;
+        } else if p.row > target.row {
+            p.row -= 1;
+        }
+        if p.col < target.col {
+            p.col += 1;
+        } else if p.col > target.col {
+            p.col -= 1;
+        }
+    }
+}
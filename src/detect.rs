@@ -0,0 +1,342 @@
+use std::fmt::Display;
+
+use anyhow::bail;
+
+use crate::input::Input;
+
+/// Below this confidence, an input is taken not to belong to a day.
+const MISMATCH: f64 = 0.5;
+
+/// A day that an input might belong to.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Guess {
+    pub day: usize,
+    /// From 0 (nothing like it) to 1 (every line fits).
+    pub confidence: f64,
+}
+
+impl Display for Guess {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "day {} ({:.0}%)", self.day, self.confidence * 100.0)
+    }
+}
+
+/// Every day that `input` looks like, most likely first.
+pub fn detect(input: &Input) -> Vec<Guess> {
+    let lines = input.lines().collect::<Vec<_>>();
+    let mut guesses = SIGNATURES
+        .iter()
+        .enumerate()
+        .map(|(i, signature)| Guess {
+            day: i + 1,
+            confidence: signature(&lines),
+        })
+        .filter(|g| g.confidence > 0.0)
+        .collect::<Vec<_>>();
+    guesses.sort_by(|a, b| b.confidence.total_cmp(&a.confidence));
+    guesses
+}
+
+/// Fails if `input` looks like it belongs to a different day than `day`.
+pub fn check(day: usize, input: &Input) -> anyhow::Result<()> {
+    let guesses = detect(input);
+    let claimed = guesses
+        .iter()
+        .find(|g| g.day == day)
+        .map_or(0.0, |g| g.confidence);
+    match guesses.first() {
+        Some(best) if best.day != day && claimed < MISMATCH => {
+            bail!("{}: Looks like {best}, not day {day}", input.name())
+        }
+        _ => Ok(()),
+    }
+}
+
+type Signature = fn(&[&str]) -> f64;
+
+const SIGNATURES: [Signature; 25] = [
+    day1, day2, day3, day4, day5, day6, day7, day8, day9, day10, day11, day12, day13, day14, day15,
+    day16, day17, day18, day19, day20, day21, day22, day23, day24, day25,
+];
+
+/// The share of non-blank lines that satisfy `fits`.
+fn fraction<F: Fn(&str) -> bool>(lines: &[&str], fits: F) -> f64 {
+    let filled = lines.iter().filter(|l| !l.trim().is_empty());
+    let total = filled.clone().count();
+    if total == 0 {
+        0.0
+    } else {
+        filled.filter(|l| fits(l)).count() as f64 / total as f64
+    }
+}
+
+fn scaled(condition: bool, scale: f64) -> f64 {
+    if condition {
+        1.0
+    } else {
+        scale
+    }
+}
+
+/// True if a blank line separates two non-blank ones.
+fn has_gaps(lines: &[&str]) -> bool {
+    let first = lines.iter().position(|l| !l.is_empty());
+    let last = lines.iter().rposition(|l| !l.is_empty());
+    match (first, last) {
+        (Some(first), Some(last)) => lines[first..last].iter().any(|l| l.is_empty()),
+        _ => false,
+    }
+}
+
+fn filled<'a>(lines: &[&'a str]) -> Vec<&'a str> {
+    lines.iter().copied().filter(|l| !l.is_empty()).collect()
+}
+
+fn same_lengths(lines: &[&str]) -> bool {
+    let filled = filled(lines);
+    filled.iter().all(|l| l.len() == filled[0].len())
+}
+
+fn is_natural(s: &str) -> bool {
+    !s.is_empty() && s.chars().all(|c| c.is_ascii_digit())
+}
+
+fn is_integer(s: &str) -> bool {
+    is_natural(s.strip_prefix('-').unwrap_or(s))
+}
+
+fn only(s: &str, allowed: &str) -> bool {
+    !s.is_empty() && s.chars().all(|c| allowed.contains(c))
+}
+
+fn day1(lines: &[&str]) -> f64 {
+    fraction(lines, is_natural) * scaled(has_gaps(lines), 0.5)
+}
+
+fn day2(lines: &[&str]) -> f64 {
+    fraction(lines, |l| {
+        let c = l.as_bytes();
+        c.len() == 3 && b"ABC".contains(&c[0]) && c[1] == b' ' && b"XYZ".contains(&c[2])
+    })
+}
+
+fn day3(lines: &[&str]) -> f64 {
+    let rucksack =
+        |l: &str| l.len().is_multiple_of(2) && l.chars().all(|c| c.is_ascii_alphabetic());
+    fraction(lines, rucksack)
+        * scaled(filled(lines).len() > 1, 0.5)
+        * scaled(day12(lines) == 0.0, 0.5)
+}
+
+fn day4(lines: &[&str]) -> f64 {
+    let range = |r: &str| {
+        r.split_once('-')
+            .is_some_and(|(a, b)| is_natural(a) && is_natural(b))
+    };
+    fraction(lines, |l| {
+        l.split_once(',').is_some_and(|(a, b)| range(a) && range(b))
+    })
+}
+
+fn is_crane_move(line: &str) -> bool {
+    let words = line.split(' ').collect::<Vec<_>>();
+    words.len() == 6
+        && [words[0], words[2], words[4]] == ["move", "from", "to"]
+        && [words[1], words[3], words[5]].iter().all(|w| is_natural(w))
+}
+
+fn day5(lines: &[&str]) -> f64 {
+    let crates = |l: &str| {
+        l.as_bytes()
+            .chunks(4)
+            .all(|c| matches!(c, [b'[', _, b']', ..]) || c.iter().all(|b| *b == b' '))
+    };
+    let labels = |l: &str| l.split_whitespace().all(is_natural);
+    fraction(lines, |l| is_crane_move(l) || crates(l) || labels(l))
+        * scaled(lines.iter().any(|l| is_crane_move(l)), 0.5)
+}
+
+fn day6(lines: &[&str]) -> f64 {
+    let filled = filled(lines);
+    let signal = filled.len() == 1
+        && filled[0].len() >= 4
+        && filled[0].chars().all(|c| c.is_ascii_lowercase());
+    scaled(signal, 0.0)
+}
+
+fn day7(lines: &[&str]) -> f64 {
+    let entry = |l: &str| match l.split_once(' ') {
+        Some(("$", command)) => command == "ls" || command.starts_with("cd "),
+        Some(("dir", _)) => true,
+        Some((size, _)) => is_natural(size),
+        None => false,
+    };
+    fraction(lines, entry) * scaled(lines.first().is_some_and(|l| l.starts_with("$ cd")), 0.8)
+}
+
+fn day8(lines: &[&str]) -> f64 {
+    let grid = same_lengths(lines) && !has_gaps(lines) && filled(lines).len() > 1;
+    fraction(lines, |l| l.len() > 1 && is_natural(l)) * scaled(grid, 0.3)
+}
+
+fn day9(lines: &[&str]) -> f64 {
+    fraction(lines, |l| {
+        l.split_once(' ')
+            .is_some_and(|(dir, n)| only(dir, "UDLR") && dir.len() == 1 && is_natural(n))
+    })
+}
+
+fn day10(lines: &[&str]) -> f64 {
+    fraction(lines, |l| {
+        l == "noop" || l.strip_prefix("addx ").is_some_and(is_integer)
+    })
+}
+
+fn day11(lines: &[&str]) -> f64 {
+    const PREFIXES: [&str; 6] = [
+        "Monkey ",
+        "Starting items:",
+        "Operation: new = ",
+        "Test: divisible by ",
+        "If true: throw to monkey ",
+        "If false: throw to monkey ",
+    ];
+    fraction(lines, |l| PREFIXES.iter().any(|p| l.trim().starts_with(p)))
+}
+
+fn day12(lines: &[&str]) -> f64 {
+    let count = |c| lines.iter().map(|l| l.matches(c).count()).sum::<usize>();
+    let heights = fraction(lines, |l| {
+        l.chars()
+            .all(|c| c.is_ascii_lowercase() || c == 'S' || c == 'E')
+    });
+    heights
+        * scaled(
+            count('S') == 1 && count('E') == 1 && same_lengths(lines),
+            0.0,
+        )
+}
+
+fn day13(lines: &[&str]) -> f64 {
+    fraction(lines, |l| {
+        l.starts_with('[') && l.ends_with(']') && only(l, "[],0123456789")
+    })
+}
+
+fn is_point(s: &str) -> bool {
+    s.split_once(',')
+        .is_some_and(|(a, b)| is_natural(a) && is_natural(b))
+}
+
+fn day14(lines: &[&str]) -> f64 {
+    fraction(lines, |l| l.split(" -> ").all(is_point))
+}
+
+fn day15(lines: &[&str]) -> f64 {
+    fraction(lines, |l| {
+        l.starts_with("Sensor at x=") && l.contains(": closest beacon is at x=")
+    })
+}
+
+fn day16(lines: &[&str]) -> f64 {
+    fraction(lines, |l| {
+        l.starts_with("Valve ") && l.contains(" has flow rate=") && l.contains("; tunnel")
+    })
+}
+
+fn day17(lines: &[&str]) -> f64 {
+    let filled = filled(lines);
+    scaled(filled.len() == 1 && only(filled[0], "<>"), 0.0)
+}
+
+fn day18(lines: &[&str]) -> f64 {
+    fraction(lines, |l| {
+        let coordinates = l.split(',').collect::<Vec<_>>();
+        coordinates.len() == 3 && coordinates.iter().all(|c| is_integer(c))
+    })
+}
+
+fn day19(lines: &[&str]) -> f64 {
+    fraction(lines, |l| {
+        let l = l.trim();
+        l.starts_with("Blueprint ") || (l.starts_with("Each ") && l.contains(" robot costs "))
+    })
+}
+
+fn day20(lines: &[&str]) -> f64 {
+    let zeros = lines.iter().filter(|l| **l == "0").count();
+    fraction(lines, is_integer) * scaled(zeros == 1 && !has_gaps(lines), 0.3)
+}
+
+fn day21(lines: &[&str]) -> f64 {
+    let name = |s: &str| !s.is_empty() && s.chars().all(|c| c.is_ascii_lowercase());
+    fraction(lines, |l| match l.split_once(": ") {
+        Some((monkey, job)) if name(monkey) => {
+            let words = job.split(' ').collect::<Vec<_>>();
+            match words.as_slice() {
+                [n] => is_integer(n),
+                [a, op, b] => name(a) && only(op, "+-*/") && op.len() == 1 && name(b),
+                _ => false,
+            }
+        }
+        _ => false,
+    })
+}
+
+fn day22(lines: &[&str]) -> f64 {
+    let path = |l: &str| only(l, "0123456789LR");
+    fraction(lines, |l| only(l, " .#") || path(l))
+        * scaled(lines.iter().any(|l| path(l)) && has_gaps(lines), 0.5)
+}
+
+/// True if every line starts and ends with a wall, as the blizzard basin does.
+fn walled(lines: &[&str]) -> bool {
+    let filled = filled(lines);
+    !filled.is_empty()
+        && filled
+            .iter()
+            .all(|l| l.starts_with('#') && l.ends_with('#'))
+}
+
+fn day23(lines: &[&str]) -> f64 {
+    fraction(lines, |l| only(l, ".#")) * scaled(!walled(lines), 0.5)
+}
+
+fn day24(lines: &[&str]) -> f64 {
+    let filled = filled(lines);
+    let doorway = |l: &&str| l.matches('.').count() == 1 && only(l, ".#");
+    let ends = filled.first().is_some_and(doorway) && filled.last().is_some_and(doorway);
+    fraction(lines, |l| only(l, "#.<>^v")) * scaled(walled(lines) && ends, 0.5)
+}
+
+fn day25(lines: &[&str]) -> f64 {
+    let negative = lines.iter().any(|l| l.contains('-') || l.contains('='));
+    fraction(lines, |l| only(l, "012-=")) * scaled(negative, 0.4)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        detect::{check, detect},
+        input::Input,
+        runner::{default_input, NUM_DAYS},
+    };
+
+    #[test]
+    fn test_examples() {
+        for day in 1..=NUM_DAYS {
+            let input = Input::from_file(default_input(day).as_str()).unwrap();
+            let guesses = detect(&input);
+            assert_eq!(guesses[0].day, day, "{guesses:?}");
+            assert!(check(day, &input).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_mislabeled() {
+        let input = Input::from_file("ex/day5.txt").unwrap();
+        let error = check(7, &input).unwrap_err().to_string();
+        assert_eq!(error, "ex/day5.txt: Looks like day 5 (100%), not day 7");
+        assert!(detect(&Input::from("")).is_empty());
+    }
+}
@@ -0,0 +1,769 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Write,
+};
+
+use anyhow::bail;
+
+use crate::day25::Snafu;
+
+/// A small deterministic generator (splitmix64), so that a seed always
+/// reproduces the same input.
+#[derive(Clone, Debug)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    /// A value in `0..n`.
+    pub fn below(&mut self, n: usize) -> usize {
+        assert!(n > 0);
+        (self.next_u64() % n as u64) as usize
+    }
+
+    /// A value in `lo..=hi`.
+    pub fn range(&mut self, lo: i64, hi: i64) -> i64 {
+        assert!(lo <= hi);
+        lo + (self.next_u64() % (hi - lo + 1) as u64) as i64
+    }
+
+    /// True once in every `n` calls, on average.
+    pub fn one_in(&mut self, n: usize) -> bool {
+        self.below(n) == 0
+    }
+
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+
+    fn letter(&mut self) -> char {
+        (b'a' + self.below(26) as u8) as char
+    }
+
+    fn word(&mut self, min_len: usize, max_len: usize) -> String {
+        let len = min_len + self.below(max_len - min_len + 1);
+        (0..len).map(|_| self.letter()).collect()
+    }
+}
+
+/// A random well-formed input for `day`. `size` scales the input, roughly as
+/// its number of records (lines, groups, monkeys, valves...), and `seed`
+/// picks one input among those of that size.
+pub fn generate(day: usize, size: usize, seed: u64) -> anyhow::Result<String> {
+    let mut rng = Rng::new(seed);
+    let size = size.max(1);
+    Ok(match day {
+        1 => day1(&mut rng, size),
+        2 => day2(&mut rng, size),
+        3 => day3(&mut rng, size),
+        4 => day4(&mut rng, size),
+        5 => day5(&mut rng, size),
+        6 => day6(&mut rng, size),
+        7 => day7(&mut rng, size),
+        8 => day8(&mut rng, size),
+        9 => day9(&mut rng, size),
+        10 => day10(&mut rng, size),
+        11 => day11(&mut rng, size),
+        12 => day12(&mut rng, size),
+        13 => day13(&mut rng, size),
+        14 => day14(&mut rng, size),
+        15 => day15(&mut rng, size),
+        16 => day16(&mut rng, size),
+        17 => day17(&mut rng, size),
+        18 => day18(&mut rng, size),
+        19 => day19(&mut rng, size),
+        20 => day20(&mut rng, size),
+        21 => day21(&mut rng, size),
+        22 => day22(&mut rng, size),
+        23 => day23(&mut rng, size),
+        24 => day24(&mut rng, size),
+        25 => day25(&mut rng, size),
+        _ => bail!("No generator for day {day}"),
+    })
+}
+
+fn lines<I: Iterator<Item = String>>(lines: I) -> String {
+    lines.collect::<Vec<_>>().join("\n")
+}
+
+/// At least three elves, for the top three.
+fn day1(rng: &mut Rng, size: usize) -> String {
+    lines((0..size.max(3)).map(|_| {
+        let snacks = 1 + rng.below(6);
+        lines((0..snacks).map(|_| rng.range(1000, 60000).to_string())) + "\n"
+    }))
+}
+
+fn day2(rng: &mut Rng, size: usize) -> String {
+    lines((0..size).map(|_| {
+        format!(
+            "{} {}",
+            rng.pick(&['A', 'B', 'C']),
+            rng.pick(&['X', 'Y', 'Z'])
+        )
+    }))
+}
+
+/// Each rucksack shares exactly one item between its compartments, and each
+/// group of three shares exactly one badge.
+fn day3(rng: &mut Rng, size: usize) -> String {
+    let items = ('a'..='z').chain('A'..='Z').collect::<Vec<_>>();
+    let mut rucksacks = vec![];
+    for _ in 0..size.div_ceil(3) {
+        let mut items = items.clone();
+        rng.shuffle(&mut items);
+        let badge = items.pop().unwrap();
+        for pool in items.chunks(items.len() / 3) {
+            let mut pool = pool.to_vec();
+            rng.shuffle(&mut pool);
+            let shared = if rng.one_in(3) {
+                badge
+            } else {
+                pool.pop().unwrap()
+            };
+            let (left, right) = pool.split_at(pool.len() / 2);
+            let mut halves = [vec![shared], vec![shared]];
+            if shared != badge {
+                halves[rng.below(2)].push(badge);
+            }
+            let len = 2 + rng.below(12);
+            for (half, items) in halves.iter_mut().zip([left, right]) {
+                while half.len() < len {
+                    half.push(*rng.pick(items));
+                }
+                rng.shuffle(half);
+            }
+            rucksacks.push(halves.concat().iter().collect::<String>());
+        }
+    }
+    lines(rucksacks.into_iter())
+}
+
+fn day4(rng: &mut Rng, size: usize) -> String {
+    let mut range = || {
+        let start = rng.range(1, 99);
+        let end = rng.range(start, 99);
+        format!("{start}-{end}")
+    };
+    lines((0..size).map(|_| {
+        let first = range();
+        format!("{first},{}", range())
+    }))
+}
+
+/// Moves never empty a stack, so every stack has a top crate at the end.
+fn day5(rng: &mut Rng, size: usize) -> String {
+    let num_stacks = 2 + rng.below(8);
+    let mut stacks = (0..num_stacks)
+        .map(|_| {
+            (0..1 + rng.below(8))
+                .map(|_| rng.letter().to_ascii_uppercase())
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    let mut result = vec![];
+    let height = stacks.iter().map(|s| s.len()).max().unwrap();
+    for row in (0..height).rev() {
+        let cells = stacks
+            .iter()
+            .map(|s| s.get(row).map_or("   ".to_owned(), |c| format!("[{c}]")))
+            .collect::<Vec<_>>();
+        result.push(cells.join(" "));
+    }
+    let numbers = (1..=num_stacks)
+        .map(|i| format!(" {i} "))
+        .collect::<Vec<_>>();
+    result.push(numbers.join(" "));
+    result.push(String::new());
+    for _ in 0..size {
+        let from = loop {
+            let from = rng.below(num_stacks);
+            if stacks[from].len() > 1 || stacks.iter().all(|s| s.len() == 1) {
+                break from;
+            }
+        };
+        if stacks[from].len() == 1 {
+            break;
+        }
+        let to = (from + 1 + rng.below(num_stacks - 1)) % num_stacks;
+        let count = 1 + rng.below(stacks[from].len() - 1);
+        let start = stacks[from].len() - count;
+        let moved = stacks[from].split_off(start);
+        stacks[to].extend(moved);
+        result.push(format!("move {count} from {} to {}", from + 1, to + 1));
+    }
+    lines(result.into_iter())
+}
+
+/// Has a start-of-message marker (and so a start-of-packet marker) after
+/// about `size` characters.
+fn day6(rng: &mut Rng, size: usize) -> String {
+    let alphabet = 4 + rng.below(10);
+    let mut result = (0..size)
+        .map(|_| (b'a' + rng.below(alphabet) as u8) as char)
+        .collect::<String>();
+    let mut letters = ('a'..='z').collect::<Vec<_>>();
+    rng.shuffle(&mut letters);
+    result.extend(letters[..14].iter());
+    result.extend((0..rng.below(size)).map(|_| rng.letter()));
+    result
+}
+
+struct Directory {
+    files: Vec<(String, usize)>,
+    subdirectories: Vec<(String, usize)>,
+}
+
+/// Keeps the total size within the 70000000 disk, so that some directory is
+/// always big enough to delete.
+fn day7(rng: &mut Rng, size: usize) -> String {
+    const DISK: usize = 70000000;
+    const UNUSED_NEEDED: usize = 30000000;
+    let largest_file = DISK / (size + 1);
+    loop {
+        let mut directories = vec![Directory {
+            files: vec![],
+            subdirectories: vec![],
+        }];
+        let mut names = BTreeSet::new();
+        for _ in 0..size {
+            let parent = rng.below(directories.len());
+            let name = rng.word(1, 8);
+            if !names.insert((parent, name.clone())) {
+                continue;
+            }
+            if directories.len() == 1 || rng.one_in(3) {
+                let id = directories.len();
+                directories[parent].subdirectories.push((name, id));
+                directories.push(Directory {
+                    files: vec![],
+                    subdirectories: vec![],
+                });
+            } else {
+                let name = if rng.one_in(2) {
+                    format!("{name}.{}", rng.word(1, 3))
+                } else {
+                    name
+                };
+                let file_size = if rng.one_in(2) {
+                    rng.range(1, 50000) as usize
+                } else {
+                    rng.range(1, largest_file as i64) as usize
+                };
+                directories[parent].files.push((name, file_size));
+            }
+        }
+        let sizes = directory_sizes(&directories);
+        let needed = (sizes[0] + UNUSED_NEEDED).saturating_sub(DISK);
+        if sizes[1..].iter().any(|s| *s >= needed) {
+            let mut result = vec!["$ cd /".to_owned()];
+            list_directory(rng, &directories, 0, &mut result);
+            return lines(result.into_iter());
+        }
+    }
+}
+
+fn directory_sizes(directories: &[Directory]) -> Vec<usize> {
+    let mut sizes = vec![0; directories.len()];
+    for (i, directory) in directories.iter().enumerate().rev() {
+        sizes[i] = directory.files.iter().map(|(_, size)| size).sum::<usize>()
+            + directory
+                .subdirectories
+                .iter()
+                .map(|(_, d)| sizes[*d])
+                .sum::<usize>();
+    }
+    sizes
+}
+
+fn list_directory(rng: &mut Rng, directories: &[Directory], i: usize, result: &mut Vec<String>) {
+    result.push("$ ls".to_owned());
+    let mut entries = directories[i]
+        .subdirectories
+        .iter()
+        .map(|(name, _)| format!("dir {name}"))
+        .chain(
+            directories[i]
+                .files
+                .iter()
+                .map(|(name, size)| format!("{size} {name}")),
+        )
+        .collect::<Vec<_>>();
+    rng.shuffle(&mut entries);
+    result.extend(entries);
+    for (name, subdirectory) in directories[i].subdirectories.iter() {
+        result.push(format!("$ cd {name}"));
+        list_directory(rng, directories, *subdirectory, result);
+        result.push("$ cd ..".to_owned());
+    }
+}
+
+fn day8(rng: &mut Rng, size: usize) -> String {
+    lines((0..size).map(|_| (0..size).map(|_| rng.below(10).to_string()).collect()))
+}
+
+fn day9(rng: &mut Rng, size: usize) -> String {
+    lines((0..size).map(|_| format!("{} {}", rng.pick(&['R', 'L', 'U', 'D']), rng.range(1, 20))))
+}
+
+/// Runs for at least the 240 cycles that the screen draws.
+fn day10(rng: &mut Rng, size: usize) -> String {
+    let mut result = vec![];
+    let mut cycles = 0;
+    while cycles < size.max(240) {
+        if rng.one_in(3) {
+            result.push("noop".to_owned());
+            cycles += 1;
+        } else {
+            let mut v = rng.range(-20, 19);
+            if v >= 0 {
+                v += 1;
+            }
+            result.push(format!("addx {v}"));
+            cycles += 2;
+        }
+    }
+    lines(result.into_iter())
+}
+
+/// Between 2 and 8 monkeys, with distinct prime divisors and at most one
+/// monkey that squares its worry levels.
+fn day11(rng: &mut Rng, size: usize) -> String {
+    let num_monkeys = size.clamp(2, 8);
+    let mut primes = vec![2, 3, 5, 7, 11, 13, 17, 19, 23];
+    rng.shuffle(&mut primes);
+    let squarer = rng.below(num_monkeys);
+    let monkeys = (0..num_monkeys).map(|i| {
+        let items = (0..1 + rng.below(6))
+            .map(|_| rng.range(50, 99).to_string())
+            .collect::<Vec<_>>();
+        let operation = if i == squarer {
+            "old * old".to_owned()
+        } else if rng.one_in(2) {
+            format!("old * {}", rng.range(2, 19))
+        } else {
+            format!("old + {}", rng.range(1, 8))
+        };
+        let if_true = (i + 1 + rng.below(num_monkeys - 1)) % num_monkeys;
+        let mut if_false = (i + 1 + rng.below(num_monkeys - 1)) % num_monkeys;
+        if if_false == if_true && num_monkeys > 2 {
+            if_false = (if_false + 1) % num_monkeys;
+            if if_false == i {
+                if_false = (if_false + 1) % num_monkeys;
+            }
+        }
+        format!(
+            "Monkey {i}:\n  Starting items: {}\n  Operation: new = {operation}\n  Test: divisible by {}\n    If true: throw to monkey {if_true}\n    If false: throw to monkey {if_false}\n",
+            items.join(", "),
+            primes[i]
+        )
+    });
+    lines(monkeys)
+}
+
+/// Low ground with scattered hills, and a ramp along the bottom row up to E,
+/// so that E is always reachable from S.
+fn day12(rng: &mut Rng, size: usize) -> String {
+    let width = size + 27;
+    let height = size / 3 + 2;
+    let mut rows = vec![];
+    for _ in 0..height - 1 {
+        rows.push(
+            (0..width)
+                .map(|_| {
+                    if rng.one_in(8) {
+                        (b'a' + rng.below(26) as u8) as char
+                    } else {
+                        *rng.pick(&['a', 'b'])
+                    }
+                })
+                .collect::<String>(),
+        );
+    }
+    let mut bottom = String::from("S");
+    bottom.extend((1..width - 26).map(|_| 'a'));
+    bottom.extend('b'..='z');
+    bottom.push('E');
+    rows.push(bottom);
+    lines(rows.into_iter())
+}
+
+fn packet(rng: &mut Rng, depth: usize) -> String {
+    let items = (0..rng.below(5))
+        .map(|_| {
+            if depth < 3 && rng.one_in(3) {
+                packet(rng, depth + 1)
+            } else {
+                rng.range(0, 10).to_string()
+            }
+        })
+        .collect::<Vec<_>>();
+    format!("[{}]", items.join(","))
+}
+
+fn day13(rng: &mut Rng, size: usize) -> String {
+    lines((0..size).map(|_| format!("{}\n{}\n", packet(rng, 0), packet(rng, 0))))
+}
+
+/// Rock paths of horizontal and vertical segments, all below the sand source.
+fn day14(rng: &mut Rng, size: usize) -> String {
+    let depth = 10 + size as i64;
+    lines((0..size).map(|_| {
+        let mut x = rng.range(470, 530);
+        let mut y = rng.range(1, depth);
+        let mut points = vec![format!("{x},{y}")];
+        let mut horizontal = rng.one_in(2);
+        for _ in 0..1 + rng.below(4) {
+            if horizontal {
+                x = (x + rng.range(-6, 6)).clamp(460, 540);
+            } else {
+                y = (y + rng.range(-6, 6)).clamp(1, depth);
+            }
+            horizontal = !horizontal;
+            points.push(format!("{x},{y}"));
+        }
+        points.join(" -> ")
+    }))
+}
+
+/// Leaves exactly one uncovered position within the full-size search area,
+/// boxed in by four sensors placed diagonally around it.
+fn day15(rng: &mut Rng, size: usize) -> String {
+    const LIMIT: i64 = 4000000;
+    const OFFSET: i64 = LIMIT + 1;
+    let (hx, hy) = (rng.range(0, LIMIT), rng.range(0, LIMIT));
+    let mut sensors = vec![];
+    for (dx, dy) in [(1, 1), (1, -1), (-1, 1), (-1, -1)] {
+        let (sx, sy) = (hx + dx * OFFSET, hy + dy * OFFSET);
+        sensors.push(((sx, sy), (sx - dx * (2 * OFFSET - 1), sy)));
+    }
+    while sensors.len() < size.max(4) {
+        let (sx, sy) = (rng.range(0, LIMIT), rng.range(0, LIMIT));
+        let to_hole = (sx - hx).abs() + (sy - hy).abs();
+        if to_hole < 2 {
+            continue;
+        }
+        let radius = rng.range(1, (to_hole - 1).min(LIMIT / 4));
+        let bx = rng.range(0, radius);
+        let by = radius - bx;
+        let bx = if rng.one_in(2) { sx + bx } else { sx - bx };
+        let by = if rng.one_in(2) { sy + by } else { sy - by };
+        sensors.push(((sx, sy), (bx, by)));
+    }
+    rng.shuffle(&mut sensors);
+    lines(sensors.iter().map(|((sx, sy), (bx, by))| {
+        format!("Sensor at x={sx}, y={sy}: closest beacon is at x={bx}, y={by}")
+    }))
+}
+
+/// A connected tunnel network starting at AA, with at most 10 working valves.
+fn day16(rng: &mut Rng, size: usize) -> String {
+    let num_valves = size.clamp(2, 26 * 26);
+    let mut names = BTreeSet::new();
+    names.insert("AA".to_owned());
+    while names.len() < num_valves {
+        let name = rng.word(2, 2).to_ascii_uppercase();
+        names.insert(name);
+    }
+    let mut names = names.into_iter().collect::<Vec<_>>();
+    rng.shuffle(&mut names[1..]);
+    let mut tunnels: BTreeMap<usize, BTreeSet<usize>> = BTreeMap::new();
+    for i in 1..num_valves {
+        let j = rng.below(i);
+        tunnels.entry(i).or_default().insert(j);
+        tunnels.entry(j).or_default().insert(i);
+    }
+    for _ in 0..num_valves / 3 {
+        let (i, j) = (rng.below(num_valves), rng.below(num_valves));
+        if i != j {
+            tunnels.entry(i).or_default().insert(j);
+            tunnels.entry(j).or_default().insert(i);
+        }
+    }
+    let working = (num_valves - 1).min(10);
+    let mut valves = (0..num_valves)
+        .map(|i| {
+            let flow = if i > 0 && i <= working {
+                rng.range(1, 25)
+            } else {
+                0
+            };
+            let neighbors = tunnels[&i]
+                .iter()
+                .map(|j| names[*j].as_str())
+                .collect::<Vec<_>>();
+            let plural = if neighbors.len() == 1 {
+                "tunnel leads to valve"
+            } else {
+                "tunnels lead to valves"
+            };
+            format!(
+                "Valve {} has flow rate={flow}; {plural} {}",
+                names[i],
+                neighbors.join(", ")
+            )
+        })
+        .collect::<Vec<_>>();
+    rng.shuffle(&mut valves);
+    lines(valves.into_iter())
+}
+
+fn day17(rng: &mut Rng, size: usize) -> String {
+    (0..size).map(|_| *rng.pick(&['<', '>'])).collect()
+}
+
+fn day18(rng: &mut Rng, size: usize) -> String {
+    let extent = 2 + (2.0 * size as f64).cbrt() as i64;
+    let size = size.min((extent * extent * extent) as usize);
+    let mut cubes = BTreeSet::new();
+    while cubes.len() < size {
+        cubes.insert((
+            rng.range(1, extent),
+            rng.range(1, extent),
+            rng.range(1, extent),
+        ));
+    }
+    let mut cubes = cubes.into_iter().collect::<Vec<_>>();
+    rng.shuffle(&mut cubes);
+    lines(cubes.iter().map(|(x, y, z)| format!("{x},{y},{z}")))
+}
+
+fn day19(rng: &mut Rng, size: usize) -> String {
+    lines((1..=size).map(|i| {
+        format!(
+            "Blueprint {i}: Each ore robot costs {} ore. Each clay robot costs {} ore. Each obsidian robot costs {} ore and {} clay. Each geode robot costs {} ore and {} obsidian.",
+            rng.range(2, 4),
+            rng.range(2, 4),
+            rng.range(2, 4),
+            rng.range(5, 20),
+            rng.range(2, 4),
+            rng.range(7, 20)
+        )
+    }))
+}
+
+/// Exactly one zero, as the coordinates are counted from it.
+fn day20(rng: &mut Rng, size: usize) -> String {
+    let mut nums = vec![0];
+    while nums.len() < size.max(3) {
+        let num = rng.range(-10000, 10000);
+        if num != 0 {
+            nums.push(num);
+        }
+    }
+    rng.shuffle(&mut nums);
+    lines(nums.iter().map(|n| n.to_string()))
+}
+
+enum Job {
+    Value(i64),
+    Oper(usize, char, usize),
+}
+
+/// An expression tree in which every division is exact and both sides of
+/// root are equal, so that humn already shouts the part 2 answer.
+fn day21(rng: &mut Rng, size: usize) -> String {
+    let mut jobs = vec![];
+    let goal = rng.range(1, 1000);
+    let budget = size.max(3) - 1;
+    let left_budget = budget / 2;
+    let left = monkey_tree(rng, goal, left_budget, &mut jobs);
+    let human_candidates = jobs
+        .iter()
+        .enumerate()
+        .filter(|(_, job)| matches!(job, Job::Value(_)))
+        .map(|(i, _)| i)
+        .collect::<Vec<_>>();
+    let human = *rng.pick(&human_candidates);
+    let right = monkey_tree(rng, goal, budget - left_budget, &mut jobs);
+    let root = jobs.len();
+    jobs.push(Job::Oper(left, '+', right));
+
+    let mut names = BTreeSet::new();
+    while names.len() < jobs.len() {
+        let name = rng.word(4, 4);
+        if name != "root" && name != "humn" {
+            names.insert(name);
+        }
+    }
+    let mut names = names.into_iter().collect::<Vec<_>>();
+    rng.shuffle(&mut names);
+    names[root] = "root".to_owned();
+    names[human] = "humn".to_owned();
+
+    let mut result = jobs
+        .iter()
+        .enumerate()
+        .map(|(i, job)| match job {
+            Job::Value(v) => format!("{}: {v}", names[i]),
+            Job::Oper(a, op, b) => format!("{}: {} {op} {}", names[i], names[*a], names[*b]),
+        })
+        .collect::<Vec<_>>();
+    rng.shuffle(&mut result);
+    lines(result.into_iter())
+}
+
+/// Adds a tree of about `budget` monkeys that evaluates to `value`, returning
+/// the index of its top monkey.
+fn monkey_tree(rng: &mut Rng, value: i64, budget: usize, jobs: &mut Vec<Job>) -> usize {
+    const MAX_VALUE: i64 = 1_000_000_000_000;
+    if budget < 3 {
+        jobs.push(Job::Value(value));
+        return jobs.len() - 1;
+    }
+    let (op, left_value, right_value) = match rng.below(4) {
+        0 if value > 1 => {
+            let left = rng.range(1, value - 1);
+            ('+', left, value - left)
+        }
+        1 if value < MAX_VALUE => {
+            let right = rng.range(1, 1000);
+            ('-', value + right, right)
+        }
+        2 if value < MAX_VALUE => {
+            let right = rng.range(2, 4);
+            ('/', value * right, right)
+        }
+        _ => {
+            let divisors = (1..=5).filter(|d| value % d == 0).collect::<Vec<_>>();
+            let right = *rng.pick(&divisors);
+            ('*', value / right, right)
+        }
+    };
+    let budget = budget - 1;
+    let left_budget = budget / 4 + rng.below(budget / 2 + 1);
+    let left = monkey_tree(rng, left_value, left_budget, jobs);
+    let right = monkey_tree(rng, right_value, budget - left_budget, jobs);
+    jobs.push(Job::Oper(left, op, right));
+    jobs.len() - 1
+}
+
+/// Cube nets, as rows of faces, that fold up into the board.
+const CUBE_NETS: [&[&str]; 4] = [
+    &["..#", "###", "..##"],
+    &[".##", ".#", "##", "#"],
+    &[".#", "####", ".#"],
+    &["#", "###", "..##"],
+];
+
+fn day22(rng: &mut Rng, size: usize) -> String {
+    let face = (4 + size / 20).min(50);
+    let net = rng.pick(&CUBE_NETS);
+    let mut result = vec![];
+    for (i, faces) in net.iter().enumerate() {
+        for row in 0..face {
+            let line = faces
+                .chars()
+                .map(|f| {
+                    (0..face)
+                        .map(|_| {
+                            if f == '.' {
+                                ' '
+                            } else if rng.one_in(10) {
+                                '#'
+                            } else {
+                                '.'
+                            }
+                        })
+                        .collect::<String>()
+                })
+                .collect::<String>();
+            let line = if i == 0 && row == 0 {
+                let start = line.find(|c| c != ' ').unwrap();
+                format!("{}.{}", &line[..start], &line[start + 1..])
+            } else {
+                line
+            };
+            result.push(line);
+        }
+    }
+    result.push(String::new());
+    let mut path = rng.range(1, 50).to_string();
+    for _ in 0..size {
+        write!(path, "{}{}", rng.pick(&['L', 'R']), rng.range(1, 50)).unwrap();
+    }
+    result.push(path);
+    lines(result.into_iter())
+}
+
+/// Always has an elf in the middle, so the grove is never empty.
+fn day23(rng: &mut Rng, size: usize) -> String {
+    lines((0..size).map(|row| {
+        (0..size)
+            .map(|col| {
+                if (row, col) == (size / 2, size / 2) || rng.one_in(3) {
+                    '#'
+                } else {
+                    '.'
+                }
+            })
+            .collect()
+    }))
+}
+
+/// No vertical blizzards in the entrance or exit columns, which have no
+/// walls to wrap around.
+fn day24(rng: &mut Rng, size: usize) -> String {
+    let width = size + 4;
+    let height = size / 2 + 2;
+    let mut result = vec![format!("#.{}", "#".repeat(width))];
+    for _ in 0..height {
+        let row = (0..width)
+            .map(|col| {
+                let vertical_allowed = col != 0 && col != width - 1;
+                if rng.one_in(4) {
+                    if vertical_allowed {
+                        *rng.pick(&['<', '>', '^', 'v'])
+                    } else {
+                        *rng.pick(&['<', '>'])
+                    }
+                } else {
+                    '.'
+                }
+            })
+            .collect::<String>();
+        result.push(format!("#{row}#"));
+    }
+    result.push(format!("{}.#", "#".repeat(width)));
+    lines(result.into_iter())
+}
+
+fn day25(rng: &mut Rng, size: usize) -> String {
+    lines((0..size).map(|_| {
+        let digits = rng.range(1, 15) as u32;
+        Snafu(rng.range(1, 5i64.pow(digits))).to_string()
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::generate::{generate, Rng};
+
+    #[test]
+    fn test_rng() {
+        let mut rng = Rng::new(7);
+        let values = (0..1000).map(|_| rng.range(-3, 3)).collect::<Vec<_>>();
+        assert!(values.iter().all(|v| (-3..=3).contains(v)));
+        assert!((-3..=3).all(|v| values.contains(&v)));
+        assert_eq!(generate(5, 20, 99).unwrap(), generate(5, 20, 99).unwrap());
+        assert!(generate(26, 20, 99).is_err());
+    }
+}
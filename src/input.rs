@@ -0,0 +1,82 @@
+use std::{
+    fs,
+    io::{self, Read},
+};
+
+use anyhow::Context;
+
+/// The filename that stands for standard input.
+pub const STDIN: &str = "-";
+
+/// Puzzle input text, along with the name that parse errors report it under.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Input {
+    name: String,
+    text: String,
+}
+
+impl Input {
+    pub fn new(name: &str, text: &str) -> Self {
+        Self {
+            name: name.to_owned(),
+            text: text.to_owned(),
+        }
+    }
+
+    pub fn from_file(filename: &str) -> anyhow::Result<Self> {
+        if filename == STDIN {
+            Self::stdin()
+        } else {
+            let text = fs::read_to_string(filename).with_context(|| filename.to_owned())?;
+            Ok(Self {
+                name: filename.to_owned(),
+                text,
+            })
+        }
+    }
+
+    pub fn from_reader<R: Read>(name: &str, mut reader: R) -> anyhow::Result<Self> {
+        let mut text = String::new();
+        reader
+            .read_to_string(&mut text)
+            .with_context(|| name.to_owned())?;
+        Ok(Self {
+            name: name.to_owned(),
+            text,
+        })
+    }
+
+    pub fn stdin() -> anyhow::Result<Self> {
+        Self::from_reader("<stdin>", io::stdin().lock())
+    }
+
+    pub fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    pub fn text(&self) -> &str {
+        self.text.as_str()
+    }
+
+    pub fn lines(&self) -> impl Iterator<Item = &str> {
+        self.text.lines()
+    }
+}
+
+impl From<&str> for Input {
+    fn from(text: &str) -> Self {
+        Self::new("<string>", text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::input::Input;
+
+    #[test]
+    fn test_from_reader() {
+        let input = Input::from_reader("bytes", "1000\r\n\n2000".as_bytes()).unwrap();
+        assert_eq!(input.name(), "bytes");
+        assert_eq!(input.lines().collect::<Vec<_>>(), vec!["1000", "", "2000"]);
+    }
+}
@@ -0,0 +1,79 @@
+use std::fmt::Display;
+
+use input::Input;
+use simulation::Simulation;
+
+pub mod bench;
+pub mod cli;
+pub mod cycle;
+pub mod day1;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day17;
+pub mod day18;
+pub mod day19;
+pub mod day2;
+pub mod day20;
+pub mod day21;
+pub mod day22;
+pub mod day23;
+pub mod day24;
+pub mod day25;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+pub mod detect;
+pub mod generate;
+pub mod input;
+pub mod parse;
+pub mod render;
+pub mod report;
+pub mod runner;
+pub mod simulation;
+pub mod stepper;
+
+/// A parsed puzzle input that can answer both parts of its day.
+pub trait Solution: Sized {
+    type Part1: Display;
+    type Part2: Display;
+
+    const HAS_PART_2: bool = true;
+
+    fn load(input: &Input) -> anyhow::Result<Self>;
+    fn part1(&self) -> anyhow::Result<Self::Part1>;
+    fn part2(&self) -> anyhow::Result<Self::Part2>;
+
+    /// Named values of interest beyond the answers themselves.
+    fn diagnostics(&self) -> Vec<(String, String)> {
+        vec![]
+    }
+
+    /// The puzzle's state drawn as a grid of characters, for days that have one.
+    fn drawing(&self) -> Option<String> {
+        None
+    }
+
+    /// The puzzle's starting state, for days that step through a simulation.
+    fn simulation(&self) -> Option<Box<dyn Simulation>> {
+        None
+    }
+}
+
+/// Prints both answers for `filename`, which may be `-` for standard input.
+pub fn run<S: Solution>(filename: &str) -> anyhow::Result<()> {
+    let puzzle = S::load(&Input::from_file(filename)?)?;
+    println!("Part 1: {}", puzzle.part1()?);
+    if S::HAS_PART_2 {
+        println!("Part 2: {}", puzzle.part2()?);
+    }
+    Ok(())
+}
@@ -0,0 +1,321 @@
+use std::{fmt::Display, str::FromStr};
+
+use crate::input::Input;
+
+/// A malformed input, located by file, line and column (both 1-based). The
+/// column counts characters, not bytes.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub file: String,
+    pub line: usize,
+    pub column: usize,
+    pub expected: String,
+    pub found: String,
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}:{}:{}: expected {}, found {:?}",
+            self.file, self.line, self.column, self.expected, self.found
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// A malformed line; `at` supplies the file and line once they are known.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LineError {
+    pub column: usize,
+    pub expected: String,
+    pub found: String,
+}
+
+pub type LineResult<T> = Result<T, LineError>;
+
+impl LineError {
+    pub fn new(column: usize, expected: &str, found: &str) -> Self {
+        Self {
+            column,
+            expected: expected.to_owned(),
+            found: found.to_owned(),
+        }
+    }
+
+    pub fn at(self, file: &str, line: usize) -> ParseError {
+        ParseError {
+            file: file.to_owned(),
+            line,
+            column: self.column,
+            expected: self.expected,
+            found: self.found,
+        }
+    }
+
+    pub fn shifted(self, offset: usize) -> Self {
+        Self {
+            column: self.column + offset,
+            ..self
+        }
+    }
+}
+
+impl Display for LineError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "column {}: expected {}, found {:?}",
+            self.column, self.expected, self.found
+        )
+    }
+}
+
+impl std::error::Error for LineError {}
+
+pub fn parse_lines<T, F: FnMut(&str) -> LineResult<T>>(
+    input: &Input,
+    mut parser: F,
+) -> Result<Vec<T>, ParseError> {
+    let mut result = vec![];
+    for (i, line) in input.lines().enumerate() {
+        result.push(parser(line).map_err(|e| e.at(input.name(), i + 1))?);
+    }
+    Ok(result)
+}
+
+/// A cursor over the lines of an input, for records that span several lines.
+pub struct NumberedLines<'a> {
+    name: &'a str,
+    lines: Vec<&'a str>,
+    next: usize,
+}
+
+impl<'a> NumberedLines<'a> {
+    pub fn new(input: &'a Input) -> Self {
+        Self {
+            name: input.name(),
+            lines: input.lines().collect(),
+            next: 0,
+        }
+    }
+
+    pub fn at_end(&self) -> bool {
+        self.next == self.lines.len()
+    }
+
+    pub fn error(&self, e: LineError) -> ParseError {
+        e.at(self.name, self.next)
+    }
+
+    pub fn parse_next<T, P: FnOnce(&str) -> LineResult<T>>(
+        &mut self,
+        expected: &str,
+        parser: P,
+    ) -> Result<T, ParseError> {
+        match self.lines.get(self.next) {
+            None => Err(LineError::new(1, expected, "end of file").at(self.name, self.next + 1)),
+            Some(line) => {
+                self.next += 1;
+                parser(line).map_err(|e| self.error(e))
+            }
+        }
+    }
+
+    /// Consumes a blank separator line, if the file has not already ended.
+    pub fn skip_blank(&mut self) -> Result<(), ParseError> {
+        if self.at_end() {
+            Ok(())
+        } else {
+            self.parse_next("a blank line", |line| Fields::new(line).end())
+        }
+    }
+}
+
+pub fn first_line<'a>(input: &'a Input, expected: &str) -> Result<&'a str, ParseError> {
+    input
+        .lines()
+        .next()
+        .ok_or_else(|| LineError::new(1, expected, "end of file").at(input.name(), 1))
+}
+
+/// Checks that an input is a rectangle of characters accepted by `valid`.
+pub fn check_grid<V: Fn(char) -> bool>(
+    input: &Input,
+    expected: &str,
+    valid: V,
+) -> Result<(), ParseError> {
+    let mut width = None;
+    parse_lines(input, |line| {
+        if let Some((i, c)) = line.char_indices().find(|(_, c)| !valid(*c)) {
+            return Err(LineError::new(i + 1, expected, c.to_string().as_str()));
+        }
+        let line_width = line.chars().count();
+        match width {
+            None => width = Some(line_width),
+            Some(width) if width != line_width => {
+                let expected = format!("a row of width {width}");
+                let found = format!("a row of width {line_width}");
+                return Err(LineError::new(1, expected.as_str(), found.as_str()));
+            }
+            _ => {}
+        }
+        Ok(())
+    })?;
+    Ok(())
+}
+
+/// The 1-based character column of the byte at `offset` in `line`.
+pub fn column_of(line: &str, offset: usize) -> usize {
+    line[..offset].chars().count() + 1
+}
+
+pub fn parse_token<T: FromStr>(column: usize, token: &str, expected: &str) -> LineResult<T> {
+    token
+        .parse()
+        .map_err(|_| LineError::new(column, expected, token))
+}
+
+/// The pieces of a line, each remembering the column where it starts.
+pub struct Fields<'a> {
+    line: &'a str,
+    fields: Vec<(usize, &'a str)>,
+    next: usize,
+}
+
+impl<'a> Fields<'a> {
+    pub fn new(line: &'a str) -> Self {
+        Self::from_pieces(line, line.split_whitespace())
+    }
+
+    pub fn split(line: &'a str, separators: &[char]) -> Self {
+        Self::from_pieces(line, line.split(separators).filter(|s| !s.is_empty()))
+    }
+
+    fn from_pieces<I: Iterator<Item = &'a str>>(line: &'a str, pieces: I) -> Self {
+        let fields = pieces
+            .map(|piece| {
+                let start = piece.as_ptr() as usize - line.as_ptr() as usize;
+                (column_of(line, start), piece)
+            })
+            .collect();
+        Self {
+            line,
+            fields,
+            next: 0,
+        }
+    }
+
+    pub fn end_column(&self) -> usize {
+        column_of(self.line, self.line.len())
+    }
+
+    pub fn next(&mut self, expected: &str) -> LineResult<(usize, &'a str)> {
+        match self.fields.get(self.next) {
+            Some(field) => {
+                self.next += 1;
+                Ok(*field)
+            }
+            None => Err(LineError::new(self.end_column(), expected, "end of line")),
+        }
+    }
+
+    pub fn expect(&mut self, literal: &str) -> LineResult<()> {
+        let expected = format!("{literal:?}");
+        let (column, field) = self.next(expected.as_str())?;
+        if field == literal {
+            Ok(())
+        } else {
+            Err(LineError::new(column, expected.as_str(), field))
+        }
+    }
+
+    pub fn parse<T: FromStr>(&mut self, expected: &str) -> LineResult<T> {
+        let (column, field) = self.next(expected)?;
+        parse_token(column, field, expected)
+    }
+
+    /// Parses the next field between a `prefix` and `suffix`, as in `x=2,`.
+    pub fn parse_between<T: FromStr>(
+        &mut self,
+        prefix: &str,
+        suffix: &str,
+        expected: &str,
+    ) -> LineResult<T> {
+        let expected = format!("{prefix}{expected}{suffix}");
+        let (column, field) = self.next(expected.as_str())?;
+        match field
+            .strip_prefix(prefix)
+            .and_then(|f| f.strip_suffix(suffix))
+        {
+            Some(inner) => parse_token(column + prefix.chars().count(), inner, expected.as_str()),
+            None => Err(LineError::new(column, expected.as_str(), field)),
+        }
+    }
+
+    pub fn rest(&mut self) -> Vec<(usize, &'a str)> {
+        let rest = self.fields[self.next..].to_vec();
+        self.next = self.fields.len();
+        rest
+    }
+
+    pub fn end(&self) -> LineResult<()> {
+        match self.fields.get(self.next) {
+            None => Ok(()),
+            Some((column, field)) => Err(LineError::new(*column, "end of line", field)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parse::{Fields, LineError};
+
+    #[test]
+    fn test_fields() {
+        let mut fields = Fields::new("move 3  from x");
+        assert_eq!(fields.expect("move"), Ok(()));
+        assert_eq!(fields.parse::<usize>("a quantity"), Ok(3));
+        assert_eq!(fields.expect("from"), Ok(()));
+        assert_eq!(
+            fields.parse::<usize>("a stack number"),
+            Err(LineError::new(14, "a stack number", "x"))
+        );
+        assert_eq!(
+            fields.next("more"),
+            Err(LineError::new(15, "more", "end of line"))
+        );
+    }
+
+    #[test]
+    fn test_parse_between() {
+        let mut fields = Fields::new("x=-2, y=1O:");
+        assert_eq!(fields.parse_between::<isize>("x=", ",", "an x"), Ok(-2));
+        assert_eq!(
+            fields.parse_between::<isize>("y=", ":", "a y"),
+            Err(LineError::new(9, "y=a y:", "1O"))
+        );
+    }
+
+    #[test]
+    fn test_wide_characters() {
+        let mut fields = Fields::new("déjà x=é");
+        assert_eq!(fields.expect("déjà"), Ok(()));
+        assert_eq!(
+            fields.parse_between::<isize>("x=", "", "an x"),
+            Err(LineError::new(8, "x=an x", "é"))
+        );
+        assert_eq!(
+            fields.next("more"),
+            Err(LineError::new(9, "more", "end of line"))
+        );
+    }
+
+    #[test]
+    fn test_split() {
+        let mut fields = Fields::split("2-4,6-8", &['-', ',']);
+        let columns = fields.rest().iter().map(|(c, _)| *c).collect::<Vec<_>>();
+        assert_eq!(columns, vec![1, 3, 5, 7]);
+    }
+}
@@ -0,0 +1,479 @@
+use std::{collections::BTreeMap, fmt::Write, fs, path::Path, str::FromStr};
+
+use anyhow::{anyhow, bail};
+
+use crate::{
+    input::Input,
+    parse::{parse_lines, parse_token, Fields, LineError},
+    runner::{with_day, DayAction},
+    Solution,
+};
+
+/// A rectangle of characters, as a day's `Display` draws its state.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CharGrid {
+    rows: Vec<Vec<char>>,
+    width: usize,
+}
+
+impl CharGrid {
+    /// Pads short rows with spaces, and drops blank lines at either end.
+    pub fn new(text: &str) -> Self {
+        let lines = text.lines().collect::<Vec<_>>();
+        let first = lines.iter().position(|l| !l.trim().is_empty());
+        let last = lines.iter().rposition(|l| !l.trim().is_empty());
+        let lines = match (first, last) {
+            (Some(first), Some(last)) => &lines[first..=last],
+            _ => &[],
+        };
+        let width = lines.iter().map(|l| l.chars().count()).max().unwrap_or(0);
+        let rows = lines
+            .iter()
+            .map(|l| {
+                let mut row = l.chars().collect::<Vec<_>>();
+                row.resize(width, ' ');
+                row
+            })
+            .collect();
+        Self { rows, width }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn get(&self, col: usize, row: usize) -> char {
+        self.rows[row][col]
+    }
+
+    /// Extends the grid with spaces to the right and below.
+    pub fn padded(&self, width: usize, height: usize) -> Self {
+        let mut rows = self.rows.clone();
+        rows.resize(height.max(rows.len()), vec![]);
+        let width = width.max(self.width);
+        for row in rows.iter_mut() {
+            row.resize(width, ' ');
+        }
+        Self { rows, width }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    fn hex(&self) -> String {
+        format!("#{:02x}{:02x}{:02x}", self.0, self.1, self.2)
+    }
+}
+
+impl FromStr for Rgb {
+    type Err = anyhow::Error;
+
+    /// Parses `#rrggbb`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let hex = s
+            .strip_prefix('#')
+            .filter(|h| h.len() == 6 && h.chars().all(|c| c.is_ascii_hexdigit()))
+            .ok_or_else(|| anyhow!("{s}: Not a color (expected #rrggbb)"))?;
+        let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).unwrap();
+        Ok(Self(channel(0), channel(2), channel(4)))
+    }
+}
+
+/// The color for each grid character.
+#[derive(Clone, Debug)]
+pub struct Palette {
+    colors: BTreeMap<char, Rgb>,
+    fallback: Rgb,
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        let background = Rgb(245, 245, 240);
+        let border = Rgb(120, 120, 120);
+        let wind = Rgb(90, 150, 220);
+        let mut colors = BTreeMap::from([
+            (' ', Rgb(255, 255, 255)),
+            ('.', background),
+            ('#', Rgb(40, 40, 40)),
+            ('o', Rgb(210, 180, 100)),
+            ('|', border),
+            ('-', border),
+            ('+', border),
+        ]);
+        for c in ['<', '>', '^', 'v', '2', '3', '4'] {
+            colors.insert(c, wind);
+        }
+        Self {
+            colors,
+            fallback: Rgb(220, 40, 40),
+        }
+    }
+}
+
+impl Palette {
+    pub fn with(mut self, c: char, color: Rgb) -> Self {
+        self.colors.insert(c, color);
+        self
+    }
+
+    pub fn color(&self, c: char) -> Rgb {
+        self.colors.get(&c).copied().unwrap_or(self.fallback)
+    }
+
+    /// Reads lines like `o #d2b464` over the default palette. The character
+    /// may be written `space`.
+    pub fn from_input(input: &Input) -> anyhow::Result<Self> {
+        let entries = parse_lines(input, |line| {
+            let mut fields = Fields::new(line);
+            if fields.end().is_ok() {
+                return Ok(None);
+            }
+            let (column, c) = fields.next("a character")?;
+            let c = match c {
+                "space" => ' ',
+                _ if c.chars().count() == 1 => c.chars().next().unwrap(),
+                _ => return Err(LineError::new(column, "a character or space", c)),
+            };
+            let (column, color) = fields.next("a color")?;
+            let color = parse_token(column, color, "a color like #d2b464")?;
+            fields.end()?;
+            Ok(Some((c, color)))
+        })?;
+        Ok(entries
+            .into_iter()
+            .flatten()
+            .fold(Self::default(), |palette, (c, color)| {
+                palette.with(c, color)
+            }))
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ImageFormat {
+    Ppm,
+    Png,
+    Svg,
+}
+
+impl FromStr for ImageFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "ppm" => Ok(Self::Ppm),
+            "png" => Ok(Self::Png),
+            "svg" => Ok(Self::Svg),
+            _ => bail!("{s}: Not an image format (expected ppm, png or svg)"),
+        }
+    }
+}
+
+impl ImageFormat {
+    pub fn from_path(path: &str) -> anyhow::Result<Self> {
+        match Path::new(path).extension().and_then(|e| e.to_str()) {
+            Some(extension) => extension.parse(),
+            None => bail!("{path}: No extension to choose an image format by"),
+        }
+    }
+}
+
+/// Draws a grid with one square of `cell_size` pixels per character.
+#[derive(Clone, Debug)]
+pub struct Renderer {
+    pub palette: Palette,
+    pub cell_size: usize,
+}
+
+impl Default for Renderer {
+    fn default() -> Self {
+        Self {
+            palette: Palette::default(),
+            cell_size: 8,
+        }
+    }
+}
+
+impl Renderer {
+    pub fn render(&self, grid: &CharGrid, format: ImageFormat) -> anyhow::Result<Vec<u8>> {
+        match format {
+            ImageFormat::Ppm => Ok(self.ppm(grid)),
+            ImageFormat::Png => self.png(grid),
+            ImageFormat::Svg => Ok(self.svg(grid).into_bytes()),
+        }
+    }
+
+    /// Writes an image in the format that `path`'s extension names.
+    pub fn save(&self, grid: &CharGrid, path: &str) -> anyhow::Result<()> {
+        let format = ImageFormat::from_path(path)?;
+        fs::write(path, self.render(grid, format)?)?;
+        Ok(())
+    }
+
+    fn pixel_size(&self, grid: &CharGrid) -> (usize, usize) {
+        (
+            grid.width() * self.cell_size,
+            grid.height() * self.cell_size,
+        )
+    }
+
+    /// Each pixel row as RGB bytes, top to bottom.
+    fn scanlines(&self, grid: &CharGrid) -> Vec<Vec<u8>> {
+        let mut result = vec![];
+        for row in 0..grid.height() {
+            let mut scanline = vec![];
+            for col in 0..grid.width() {
+                let Rgb(r, g, b) = self.palette.color(grid.get(col, row));
+                for _ in 0..self.cell_size {
+                    scanline.extend([r, g, b]);
+                }
+            }
+            for _ in 0..self.cell_size {
+                result.push(scanline.clone());
+            }
+        }
+        result
+    }
+
+    pub fn ppm(&self, grid: &CharGrid) -> Vec<u8> {
+        let (width, height) = self.pixel_size(grid);
+        let mut result = format!("P6\n{width} {height}\n255\n").into_bytes();
+        result.extend(self.scanlines(grid).concat());
+        result
+    }
+
+    pub fn png(&self, grid: &CharGrid) -> anyhow::Result<Vec<u8>> {
+        let mut result = self.png_start(grid)?;
+        png_chunk(&mut result, b"IDAT", &self.png_data(grid));
+        png_chunk(&mut result, b"IEND", &[]);
+        Ok(result)
+    }
+
+    /// An animated PNG, showing each frame for `delay_ms`. Frames smaller than
+    /// the largest are padded with spaces.
+    pub fn apng(&self, frames: &[CharGrid], delay_ms: u16) -> anyhow::Result<Vec<u8>> {
+        if frames.is_empty() {
+            bail!("An animated PNG needs at least one frame");
+        }
+        let width = frames.iter().map(|f| f.width()).max().unwrap_or(0);
+        let height = frames.iter().map(|f| f.height()).max().unwrap_or(0);
+        let frames = frames
+            .iter()
+            .map(|f| f.padded(width, height))
+            .collect::<Vec<_>>();
+        let first = &frames[0];
+        let mut result = self.png_start(first)?;
+        let mut control = vec![];
+        control.extend((frames.len() as u32).to_be_bytes());
+        control.extend(0u32.to_be_bytes());
+        png_chunk(&mut result, b"acTL", &control);
+        let (pixel_width, pixel_height) = self.pixel_size(first);
+        let mut sequence = 0u32;
+        for (i, frame) in frames.iter().enumerate() {
+            let mut control = vec![];
+            control.extend(sequence.to_be_bytes());
+            control.extend((pixel_width as u32).to_be_bytes());
+            control.extend((pixel_height as u32).to_be_bytes());
+            control.extend([0; 8]);
+            control.extend(delay_ms.to_be_bytes());
+            control.extend(1000u16.to_be_bytes());
+            control.extend([0, 0]);
+            png_chunk(&mut result, b"fcTL", &control);
+            sequence += 1;
+            if i == 0 {
+                png_chunk(&mut result, b"IDAT", &self.png_data(frame));
+            } else {
+                let mut data = sequence.to_be_bytes().to_vec();
+                data.extend(self.png_data(frame));
+                png_chunk(&mut result, b"fdAT", &data);
+                sequence += 1;
+            }
+        }
+        png_chunk(&mut result, b"IEND", &[]);
+        Ok(result)
+    }
+
+    /// The PNG signature and header, which cannot describe an empty image.
+    fn png_start(&self, grid: &CharGrid) -> anyhow::Result<Vec<u8>> {
+        let (width, height) = self.pixel_size(grid);
+        if width == 0 || height == 0 {
+            bail!("A PNG needs at least one pixel, not {width}x{height}");
+        }
+        let mut header = vec![];
+        header.extend((width as u32).to_be_bytes());
+        header.extend((height as u32).to_be_bytes());
+        header.extend([8, 2, 0, 0, 0]);
+        let mut result = vec![0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+        png_chunk(&mut result, b"IHDR", &header);
+        Ok(result)
+    }
+
+    fn png_data(&self, grid: &CharGrid) -> Vec<u8> {
+        let mut raw = vec![];
+        for scanline in self.scanlines(grid) {
+            raw.push(0);
+            raw.extend(scanline);
+        }
+        zlib_stored(&raw)
+    }
+
+    pub fn svg(&self, grid: &CharGrid) -> String {
+        let (width, height) = self.pixel_size(grid);
+        let size = self.cell_size;
+        let mut result = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" shape-rendering=\"crispEdges\">\n"
+        );
+        for row in 0..grid.height() {
+            for col in 0..grid.width() {
+                let color = self.palette.color(grid.get(col, row)).hex();
+                writeln!(
+                    result,
+                    "<rect x=\"{}\" y=\"{}\" width=\"{size}\" height=\"{size}\" fill=\"{color}\"/>",
+                    col * size,
+                    row * size
+                )
+                .unwrap();
+            }
+        }
+        result.push_str("</svg>\n");
+        result
+    }
+}
+
+fn png_chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    png.extend((data.len() as u32).to_be_bytes());
+    let start = png.len();
+    png.extend(kind);
+    png.extend(data);
+    let crc = crc32(&png[start..]);
+    png.extend(crc.to_be_bytes());
+}
+
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for byte in bytes {
+        crc ^= *byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xedb88320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+/// A zlib stream of uncompressed deflate blocks, which every PNG reader accepts.
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    const MAX_BLOCK: usize = 65535;
+    let mut result = vec![0x78, 0x01];
+    let blocks = data.chunks(MAX_BLOCK).collect::<Vec<_>>();
+    if blocks.is_empty() {
+        result.extend([1, 0, 0, 0xff, 0xff]);
+    }
+    for (i, block) in blocks.iter().enumerate() {
+        result.push((i + 1 == blocks.len()) as u8);
+        let len = block.len() as u16;
+        result.extend(len.to_le_bytes());
+        result.extend((!len).to_le_bytes());
+        result.extend(*block);
+    }
+    let (mut a, mut b) = (1u32, 0u32);
+    for byte in data {
+        a = (a + *byte as u32) % 65521;
+        b = (b + a) % 65521;
+    }
+    result.extend(((b << 16) | a).to_be_bytes());
+    result
+}
+
+struct Draw<'a>(&'a Input);
+
+impl DayAction for Draw<'_> {
+    type Output = anyhow::Result<Option<String>>;
+
+    fn act<S: Solution>(self) -> Self::Output {
+        Ok(S::load(self.0)?.drawing())
+    }
+}
+
+/// The grid that `day` draws for `input`.
+pub fn draw_day(day: usize, input: &Input) -> anyhow::Result<CharGrid> {
+    match with_day(day, Draw(input))?? {
+        Some(drawing) => Ok(CharGrid::new(drawing.as_str())),
+        None => bail!("Day {day} has no drawing"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        input::Input,
+        render::{crc32, draw_day, CharGrid, Palette, Renderer, Rgb},
+    };
+
+    #[test]
+    fn test_ppm() {
+        let grid = CharGrid::new("\n#.\n#\n");
+        assert_eq!((grid.width(), grid.height()), (2, 2));
+        let renderer = Renderer {
+            palette: Palette::default().with('#', Rgb(1, 2, 3)),
+            cell_size: 2,
+        };
+        let ppm = renderer.ppm(&grid);
+        let header = b"P6\n4 4\n255\n";
+        assert_eq!(&ppm[..header.len()], header);
+        assert_eq!(ppm.len(), header.len() + 4 * 4 * 3);
+        assert_eq!(&ppm[header.len()..header.len() + 3], &[1, 2, 3]);
+    }
+
+    #[test]
+    fn test_png() {
+        assert_eq!(crc32(b"IEND"), 0xae426082);
+        let grid = draw_day(14, &Input::from_file("ex/day14.txt").unwrap()).unwrap();
+        let png = Renderer::default().png(&grid).unwrap();
+        assert_eq!(&png[1..4], b"PNG");
+        assert_eq!(&png[12..16], b"IHDR");
+        let width = u32::from_be_bytes(png[16..20].try_into().unwrap());
+        assert_eq!(width as usize, grid.width() * 8);
+        assert!(png.ends_with(&[b'I', b'E', b'N', b'D', 0xae, 0x42, 0x60, 0x82]));
+
+        let error = Renderer::default().png(&CharGrid::new("")).unwrap_err();
+        assert_eq!(error.to_string(), "A PNG needs at least one pixel, not 0x0");
+        let renderer = Renderer {
+            cell_size: 0,
+            ..Renderer::default()
+        };
+        assert!(renderer.png(&grid).is_err());
+    }
+
+    #[test]
+    fn test_apng() {
+        let renderer = Renderer::default();
+        let frames = [CharGrid::new("#"), CharGrid::new("##\n.")];
+        let apng = renderer.apng(&frames, 100).unwrap();
+        let frame_count = u32::from_be_bytes(apng[41..45].try_into().unwrap());
+        assert_eq!((&apng[37..41], frame_count), (&b"acTL"[..], 2));
+        let error = renderer.apng(&[], 100).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "An animated PNG needs at least one frame"
+        );
+        assert!(renderer.apng(&[CharGrid::new("")], 100).is_err());
+    }
+
+    #[test]
+    fn test_palette() {
+        let palette = Palette::from_input(&Input::from("o #102030\n\nspace #ffffff\n")).unwrap();
+        assert_eq!(palette.color('o'), Rgb(16, 32, 48));
+        assert_eq!(palette.color(' '), Rgb(255, 255, 255));
+        assert!(Palette::from_input(&Input::from("oo #102030\n")).is_err());
+        assert!(Palette::from_input(&Input::from("o red\n")).is_err());
+    }
+}
@@ -0,0 +1,231 @@
+use std::{fmt::Write, str::FromStr, time::Duration};
+
+use anyhow::bail;
+
+use crate::runner::DayRun;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Format {
+    Table,
+    Json,
+    Csv,
+}
+
+impl FromStr for Format {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "table" => Ok(Self::Table),
+            "json" => Ok(Self::Json),
+            "csv" => Ok(Self::Csv),
+            _ => bail!("{s}: Not a format (expected table, json or csv)"),
+        }
+    }
+}
+
+impl Format {
+    /// Whether the format shows each day's diagnostics.
+    pub fn shows_diagnostics(&self) -> bool {
+        !matches!(self, Self::Table)
+    }
+
+    pub fn render(&self, runs: &[DayRun]) -> String {
+        match self {
+            Self::Table => table(runs),
+            Self::Json => json(runs),
+            Self::Csv => csv(runs),
+        }
+    }
+}
+
+fn millis(d: Duration) -> f64 {
+    d.as_secs_f64() * 1000.0
+}
+
+pub fn table(runs: &[DayRun]) -> String {
+    let mut result = String::new();
+    writeln!(result, "{:>3}  {:>4}  {:>10}  Answer", "Day", "Part", "ms").unwrap();
+    for run in runs.iter() {
+        match &run.outcome {
+            Ok(day) => {
+                for part in day.parts.iter() {
+                    let mut lines = part.answer.lines();
+                    let first = lines.next().unwrap_or("");
+                    let ms = format!("{:.3}", millis(part.elapsed));
+                    writeln!(
+                        result,
+                        "{:>3}  {:>4}  {ms:>10}  {first}",
+                        run.day, part.part
+                    )
+                    .unwrap();
+                    for line in lines {
+                        writeln!(result, "{:>3}  {:>4}  {:>10}  {line}", "", "", "").unwrap();
+                    }
+                }
+            }
+            Err(e) => {
+                writeln!(
+                    result,
+                    "{:>3}  {:>4}  {:>10}  error ({}): {e:#}",
+                    run.day, "", "", run.input
+                )
+                .unwrap();
+            }
+        }
+    }
+    result
+}
+
+pub fn json_string(s: &str) -> String {
+    let mut result = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\t' => result.push_str("\\t"),
+            c if (c as u32) < 0x20 => write!(result, "\\u{:04x}", c as u32).unwrap(),
+            c => result.push(c),
+        }
+    }
+    result.push('"');
+    result
+}
+
+pub fn json(runs: &[DayRun]) -> String {
+    let mut days = vec![];
+    for run in runs.iter() {
+        let mut fields = vec![
+            format!("\"day\":{}", run.day),
+            format!("\"input\":{}", json_string(run.input.as_str())),
+        ];
+        match &run.outcome {
+            Ok(day) => {
+                fields.push(format!("\"parse_ms\":{:.3}", millis(day.parse_elapsed)));
+                let parts = day
+                    .parts
+                    .iter()
+                    .map(|part| {
+                        format!(
+                            "{{\"part\":{},\"answer\":{},\"type\":{},\"elapsed_ms\":{:.3}}}",
+                            part.part,
+                            json_string(part.answer.as_str()),
+                            json_string(part.answer_type.as_str()),
+                            millis(part.elapsed)
+                        )
+                    })
+                    .collect::<Vec<_>>();
+                fields.push(format!("\"parts\":[{}]", parts.join(",")));
+                let diagnostics = day
+                    .diagnostics
+                    .iter()
+                    .map(|(name, value)| {
+                        format!(
+                            "{}:{}",
+                            json_string(name.as_str()),
+                            json_string(value.as_str())
+                        )
+                    })
+                    .collect::<Vec<_>>();
+                fields.push(format!("\"diagnostics\":{{{}}}", diagnostics.join(",")));
+                fields.push(format!(
+                    "\"diagnostics_ms\":{:.3}",
+                    millis(day.diagnostics_elapsed)
+                ));
+            }
+            Err(e) => fields.push(format!(
+                "\"error\":{}",
+                json_string(format!("{e:#}").as_str())
+            )),
+        }
+        days.push(format!("{{{}}}", fields.join(",")));
+    }
+    format!("[{}]\n", days.join(",\n"))
+}
+
+pub fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_owned()
+    }
+}
+
+pub fn csv(runs: &[DayRun]) -> String {
+    let mut result = String::from(
+        "day,input,part,answer,type,parse_ms,elapsed_ms,diagnostics,diagnostics_ms,error\n",
+    );
+    for run in runs.iter() {
+        let input = csv_field(run.input.as_str());
+        match &run.outcome {
+            Ok(day) => {
+                let diagnostics = day
+                    .diagnostics
+                    .iter()
+                    .map(|(name, value)| format!("{name}={value}"))
+                    .collect::<Vec<_>>()
+                    .join("; ");
+                let diagnostics = csv_field(diagnostics.as_str());
+                let parse_ms = millis(day.parse_elapsed);
+                let diagnostics_ms = millis(day.diagnostics_elapsed);
+                for part in day.parts.iter() {
+                    writeln!(
+                        result,
+                        "{},{input},{},{},{},{parse_ms:.3},{:.3},{diagnostics},{diagnostics_ms:.3},",
+                        run.day,
+                        part.part,
+                        csv_field(part.answer.as_str()),
+                        csv_field(part.answer_type.as_str()),
+                        millis(part.elapsed)
+                    )
+                    .unwrap();
+                }
+            }
+            Err(e) => {
+                let error = csv_field(format!("{e:#}").as_str());
+                writeln!(result, "{},{input},,,,,,,,{error}", run.day).unwrap();
+            }
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        report::{csv, csv_field, json_string},
+        runner::{DayRun, Part},
+    };
+
+    #[test]
+    fn test_json_string() {
+        assert_eq!(json_string("abc"), "\"abc\"");
+        assert_eq!(json_string("a\"b\\c\n#."), "\"a\\\"b\\\\c\\n#.\"");
+    }
+
+    #[test]
+    fn test_csv_field() {
+        assert_eq!(csv_field("CMZ"), "CMZ");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+    }
+
+    #[test]
+    fn test_csv() {
+        let runs = [
+            DayRun::new(6, "ex/day6a.txt", &[Part::One], true, true),
+            DayRun::new(6, "missing.txt", &[Part::One], true, true),
+        ];
+        let csv = csv(&runs);
+        let lines = csv.lines().collect::<Vec<_>>();
+        let columns = lines[0].split(',').count();
+        assert_eq!(lines[1].split(',').count(), columns);
+        assert!(lines[1].starts_with("6,ex/day6a.txt,1,7,usize,"), "{csv}");
+        assert!(
+            lines[2].starts_with("6,missing.txt,,,,,,,,missing.txt: "),
+            "{csv}"
+        );
+    }
+}
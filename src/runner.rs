@@ -0,0 +1,298 @@
+use std::{
+    any::type_name,
+    fmt::Display,
+    ops::RangeInclusive,
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use anyhow::{anyhow, bail};
+
+use crate::{
+    day1, day10, day11, day12, day13, day14, day15, day16, day17, day18, day19, day2, day20, day21,
+    day22, day23, day24, day25, day3, day4, day5, day6, day7, day8, day9, detect, input::Input,
+    Solution,
+};
+
+pub const NUM_DAYS: usize = 25;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub fn both() -> Vec<Self> {
+        vec![Self::One, Self::Two]
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let n = match self {
+            Self::One => 1,
+            Self::Two => 2,
+        };
+        write!(f, "{n}")
+    }
+}
+
+impl std::str::FromStr for Part {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1" => Ok(Self::One),
+            "2" => Ok(Self::Two),
+            _ => bail!("{s}: Not a part (expected 1 or 2)"),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct PartResult {
+    pub part: Part,
+    pub answer: String,
+    pub answer_type: String,
+    pub elapsed: Duration,
+}
+
+#[derive(Clone, Debug)]
+pub struct DayResult {
+    pub parse_elapsed: Duration,
+    pub parts: Vec<PartResult>,
+    /// Empty unless asked for, as some days rerun their solutions to find them.
+    pub diagnostics: Vec<(String, String)>,
+    pub diagnostics_elapsed: Duration,
+}
+
+pub struct DayRun {
+    pub day: usize,
+    pub input: String,
+    pub outcome: anyhow::Result<DayResult>,
+}
+
+impl DayRun {
+    /// With `detect`, refuses inputs that look like they belong to another day.
+    pub fn new(
+        day: usize,
+        filename: &str,
+        parts: &[Part],
+        include_diagnostics: bool,
+        detect: bool,
+    ) -> Self {
+        match Input::from_file(filename) {
+            Ok(input) => Self::with_input(day, &input, parts, include_diagnostics, detect),
+            Err(e) => Self {
+                day,
+                input: filename.to_owned(),
+                outcome: Err(e),
+            },
+        }
+    }
+
+    /// Solves an input that has already been read, such as standard input.
+    pub fn with_input(
+        day: usize,
+        input: &Input,
+        parts: &[Part],
+        include_diagnostics: bool,
+        detect: bool,
+    ) -> Self {
+        let checked = if detect {
+            detect::check(day, input)
+        } else {
+            Ok(())
+        };
+        Self {
+            day,
+            input: input.name().to_owned(),
+            outcome: checked.and_then(|_| solve_day(day, input, parts, include_diagnostics)),
+        }
+    }
+}
+
+fn short_type_name<T>() -> String {
+    type_name::<T>().rsplit("::").next().unwrap().to_owned()
+}
+
+fn timed<T: Display>(
+    part: Part,
+    solver: impl Fn() -> anyhow::Result<T>,
+) -> anyhow::Result<PartResult> {
+    let start = Instant::now();
+    let answer = solver()?.to_string();
+    Ok(PartResult {
+        part,
+        answer,
+        answer_type: short_type_name::<T>(),
+        elapsed: start.elapsed(),
+    })
+}
+
+pub fn solve<S: Solution>(
+    input: &Input,
+    parts: &[Part],
+    include_diagnostics: bool,
+) -> anyhow::Result<DayResult> {
+    let start = Instant::now();
+    let puzzle = S::load(input)?;
+    let parse_elapsed = start.elapsed();
+    let mut results = vec![];
+    for part in parts.iter().copied() {
+        match part {
+            Part::One => results.push(timed(part, || puzzle.part1())?),
+            Part::Two => {
+                if S::HAS_PART_2 {
+                    results.push(timed(part, || puzzle.part2())?);
+                }
+            }
+        }
+    }
+    let start = Instant::now();
+    let diagnostics = if include_diagnostics {
+        puzzle.diagnostics()
+    } else {
+        vec![]
+    };
+    Ok(DayResult {
+        parse_elapsed,
+        parts: results,
+        diagnostics,
+        diagnostics_elapsed: start.elapsed(),
+    })
+}
+
+/// Something to do with a day's `Solution` type, chosen at run time by `with_day`.
+pub trait DayAction {
+    type Output;
+
+    fn act<S: Solution>(self) -> Self::Output;
+}
+
+pub fn with_day<A: DayAction>(day: usize, action: A) -> anyhow::Result<A::Output> {
+    Ok(match day {
+        1 => action.act::<day1::Elves>(),
+        2 => action.act::<day2::StrategyGuide>(),
+        3 => action.act::<day3::Rucksacks>(),
+        4 => action.act::<day4::Assignments>(),
+        5 => action.act::<day5::CratePuzzle>(),
+        6 => action.act::<day6::Datastream>(),
+        7 => action.act::<day7::FileSystem>(),
+        8 => action.act::<day8::Forest>(),
+        9 => action.act::<day9::RopeMotions>(),
+        10 => action.act::<day10::Cpu>(),
+        11 => action.act::<day11::MonkeyTroop>(),
+        12 => action.act::<day12::HeightMap>(),
+        13 => action.act::<day13::PacketPairs>(),
+        14 => action.act::<day14::RockSection>(),
+        15 => action.act::<day15::SensorReport>(),
+        16 => action.act::<day16::TunnelGraph>(),
+        17 => action.act::<day17::JetPattern>(),
+        18 => action.act::<day18::Droplet>(),
+        19 => action.act::<day19::Costs>(),
+        20 => action.act::<day20::TrackedNums>(),
+        21 => action.act::<day21::MonkeyTroop>(),
+        22 => action.act::<day22::MonkeyNotes>(),
+        23 => action.act::<day23::CellularElves>(),
+        24 => action.act::<day24::BlizzardMap>(),
+        25 => action.act::<day25::FuelRequirements>(),
+        _ => bail!("Day {day} is not between 1 and {NUM_DAYS}"),
+    })
+}
+
+struct Solve<'a> {
+    input: &'a Input,
+    parts: &'a [Part],
+    include_diagnostics: bool,
+}
+
+impl DayAction for Solve<'_> {
+    type Output = anyhow::Result<DayResult>;
+
+    fn act<S: Solution>(self) -> Self::Output {
+        solve::<S>(self.input, self.parts, self.include_diagnostics)
+    }
+}
+
+pub fn solve_day(
+    day: usize,
+    input: &Input,
+    parts: &[Part],
+    include_diagnostics: bool,
+) -> anyhow::Result<DayResult> {
+    with_day(
+        day,
+        Solve {
+            input,
+            parts,
+            include_diagnostics,
+        },
+    )?
+}
+
+pub fn default_input(day: usize) -> String {
+    let plain = format!("ex/day{day}.txt");
+    let first_variant = format!("ex/day{day}a.txt");
+    if !Path::new(plain.as_str()).exists() && Path::new(first_variant.as_str()).exists() {
+        first_variant
+    } else {
+        plain
+    }
+}
+
+pub fn parse_days(s: &str) -> anyhow::Result<RangeInclusive<usize>> {
+    let days = if s == "all" {
+        1..=NUM_DAYS
+    } else if let Some((start, end)) = s.split_once('-') {
+        start.parse()?..=end.parse()?
+    } else {
+        let day = s.parse()?;
+        day..=day
+    };
+    if days.is_empty() || *days.start() < 1 || *days.end() > NUM_DAYS {
+        Err(anyhow!("{s}: Days must lie between 1 and {NUM_DAYS}"))
+    } else {
+        Ok(days)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        input::Input,
+        runner::{default_input, parse_days, solve_day, Part, NUM_DAYS},
+    };
+
+    #[test]
+    fn test_parse_days() {
+        assert_eq!(parse_days("7").unwrap(), 7..=7);
+        assert_eq!(parse_days("3-5").unwrap(), 3..=5);
+        assert_eq!(parse_days("all").unwrap(), 1..=NUM_DAYS);
+        assert!(parse_days("0").is_err());
+        assert!(parse_days("5-3").is_err());
+        assert!(parse_days("26").is_err());
+    }
+
+    #[test]
+    fn test_default_input() {
+        assert_eq!(default_input(5), "ex/day5.txt");
+        assert_eq!(default_input(6), "ex/day6a.txt");
+    }
+
+    #[test]
+    fn test_solve_in_memory() {
+        let input = Input::from("1000\n2000\n\n4000\n\n500\n");
+        let result = solve_day(1, &input, &Part::both(), false).unwrap();
+        assert!(result.diagnostics.is_empty());
+        let answers = result
+            .parts
+            .iter()
+            .map(|p| p.answer.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(answers, vec!["4000", "7500"]);
+        let result = solve_day(1, &input, &[Part::One], true).unwrap();
+        assert_eq!(result.diagnostics[0], ("elves".to_owned(), "3".to_owned()));
+    }
+}
@@ -0,0 +1,231 @@
+use std::{fs, path::Path, str::FromStr};
+
+use anyhow::bail;
+
+use crate::{
+    input::Input,
+    render::{CharGrid, ImageFormat, Renderer},
+    runner::{with_day, DayAction},
+    Solution,
+};
+
+/// A puzzle state that advances one tick at a time.
+pub trait Simulation {
+    /// Advances one tick, returning false once the state has stopped changing.
+    fn step(&mut self) -> bool;
+
+    /// The current state, drawn as a grid of characters.
+    fn snapshot(&self) -> String;
+}
+
+/// Steps `simulation` up to `max_ticks` times or until it stops changing.
+/// Calls `hook` with the tick number and state at the start, every `every`
+/// ticks, and at the end. Returns the number of ticks taken.
+pub fn run<S: Simulation + ?Sized, H: FnMut(usize, &S) -> anyhow::Result<()>>(
+    simulation: &mut S,
+    max_ticks: usize,
+    every: usize,
+    mut hook: H,
+) -> anyhow::Result<usize> {
+    let every = every.max(1);
+    hook(0, simulation)?;
+    let mut ticks = 0;
+    while ticks < max_ticks && simulation.step() {
+        ticks += 1;
+        if ticks % every == 0 {
+            hook(ticks, simulation)?;
+        }
+    }
+    if ticks % every != 0 {
+        hook(ticks, simulation)?;
+    }
+    Ok(ticks)
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum FrameFormat {
+    Text,
+    Image(ImageFormat),
+    AnimatedPng,
+}
+
+impl FromStr for FrameFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "txt" => Ok(Self::Text),
+            "apng" => Ok(Self::AnimatedPng),
+            _ => match s.parse() {
+                Ok(format) => Ok(Self::Image(format)),
+                Err(_) => bail!("{s}: Not a frame format (expected txt, ppm, png, svg or apng)"),
+            },
+        }
+    }
+}
+
+impl FrameFormat {
+    fn extension(&self) -> &'static str {
+        match self {
+            Self::Text => "txt",
+            Self::Image(ImageFormat::Ppm) => "ppm",
+            Self::Image(ImageFormat::Png) | Self::AnimatedPng => "png",
+            Self::Image(ImageFormat::Svg) => "svg",
+        }
+    }
+}
+
+/// Writes a simulation's snapshots as numbered frames.
+#[derive(Clone, Debug)]
+pub struct FrameExporter {
+    pub format: FrameFormat,
+    pub renderer: Renderer,
+    pub max_ticks: usize,
+    pub every: usize,
+    pub delay_ms: u16,
+}
+
+impl Default for FrameExporter {
+    fn default() -> Self {
+        Self {
+            format: FrameFormat::Text,
+            renderer: Renderer::default(),
+            max_ticks: 100,
+            every: 1,
+            delay_ms: 100,
+        }
+    }
+}
+
+impl FrameExporter {
+    /// Writes `frame_00000.txt` and onwards into the directory `path`, or a
+    /// single animated PNG file at `path`. Returns the number of frames.
+    pub fn export<S: Simulation + ?Sized>(
+        &self,
+        simulation: &mut S,
+        path: &str,
+    ) -> anyhow::Result<usize> {
+        let mut frames = vec![];
+        let mut count = 0;
+        if self.format != FrameFormat::AnimatedPng {
+            fs::create_dir_all(path)?;
+        }
+        run(simulation, self.max_ticks, self.every, |tick, state| {
+            count += 1;
+            let snapshot = state.snapshot();
+            let filename =
+                Path::new(path).join(format!("frame_{tick:05}.{}", self.format.extension()));
+            match self.format {
+                FrameFormat::Text => fs::write(filename, snapshot)?,
+                FrameFormat::Image(format) => fs::write(
+                    filename,
+                    self.renderer
+                        .render(&CharGrid::new(snapshot.as_str()), format)?,
+                )?,
+                FrameFormat::AnimatedPng => frames.push(CharGrid::new(snapshot.as_str())),
+            }
+            Ok(())
+        })?;
+        if self.format == FrameFormat::AnimatedPng {
+            fs::write(path, self.renderer.apng(&frames, self.delay_ms)?)?;
+        }
+        Ok(count)
+    }
+}
+
+struct Start<'a>(&'a Input);
+
+impl DayAction for Start<'_> {
+    type Output = anyhow::Result<Option<Box<dyn Simulation>>>;
+
+    fn act<S: Solution>(self) -> Self::Output {
+        Ok(S::load(self.0)?.simulation())
+    }
+}
+
+/// The starting state of `day`'s simulation for `input`.
+pub fn simulate_day(day: usize, input: &Input) -> anyhow::Result<Box<dyn Simulation>> {
+    match with_day(day, Start(input))?? {
+        Some(simulation) => Ok(simulation),
+        None => bail!("Day {day} has no simulation"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        env, fs,
+        path::PathBuf,
+        process,
+        sync::atomic::{AtomicUsize, Ordering},
+    };
+
+    use crate::{
+        input::Input,
+        simulation::{run, simulate_day, FrameExporter, FrameFormat},
+    };
+
+    #[test]
+    fn test_run() {
+        let input = Input::from_file("ex/day14.txt").unwrap();
+        let mut sand = simulate_day(14, &input).unwrap();
+        let mut seen = vec![];
+        let ticks = run(sand.as_mut(), 1000, 10, |tick, _| {
+            seen.push(tick);
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(ticks, 24);
+        assert_eq!(seen, vec![0, 10, 20, 24]);
+        assert!(simulate_day(1, &Input::from_file("ex/day1.txt").unwrap()).is_err());
+    }
+
+    /// A directory of its own under the system's temporary directory, removed
+    /// when dropped, so that concurrent test runs cannot clobber each other.
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new(name: &str) -> Self {
+            static COUNT: AtomicUsize = AtomicUsize::new(0);
+            let count = COUNT.fetch_add(1, Ordering::Relaxed);
+            let unique = format!("advent2022_{name}_{}_{count}", process::id());
+            let dir = env::temp_dir().join(unique);
+            fs::create_dir_all(&dir).unwrap();
+            Self(dir)
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    #[test]
+    fn test_export() {
+        let input = Input::from_file("ex/day9.txt").unwrap();
+        let temp = TempDir::new("test_export");
+        let dir = temp.0.join("frames");
+        let dir = dir.to_str().unwrap();
+        let exporter = FrameExporter::default();
+        let frames = exporter
+            .export(simulate_day(9, &input).unwrap().as_mut(), dir)
+            .unwrap();
+        assert_eq!(frames, 25);
+        let last = fs::read_to_string(format!("{dir}/frame_00024.txt")).unwrap();
+        assert!(last.contains('H'));
+
+        let apng = temp.0.join("frames.png");
+        let apng = apng.to_str().unwrap();
+        let exporter = FrameExporter {
+            format: FrameFormat::AnimatedPng,
+            ..exporter
+        };
+        let input = Input::from_file("ex/day23a.txt").unwrap();
+        exporter
+            .export(simulate_day(23, &input).unwrap().as_mut(), apng)
+            .unwrap();
+        let bytes = fs::read(apng).unwrap();
+        assert!(bytes.windows(4).any(|w| w == b"acTL"));
+    }
+}
@@ -0,0 +1,238 @@
+use std::{
+    collections::VecDeque,
+    io::{BufRead, Write},
+    str::FromStr,
+};
+
+use anyhow::bail;
+
+use crate::simulation::Simulation;
+
+/// Runs no further than this when looking for a condition.
+pub const RUN_LIMIT: usize = 1_000_000;
+
+/// A simulation with its recent snapshots, so that it can be stepped back
+/// through. Stepping forward from an earlier snapshot replays the history
+/// before running the simulation any further.
+pub struct Stepper {
+    simulation: Box<dyn Simulation>,
+    history: VecDeque<(usize, String)>,
+    capacity: usize,
+    cursor: usize,
+    stopped: bool,
+}
+
+impl Stepper {
+    /// Keeps up to `capacity` snapshots, including the current one.
+    pub fn new(simulation: Box<dyn Simulation>, capacity: usize) -> Self {
+        let history = VecDeque::from([(0, simulation.snapshot())]);
+        Self {
+            simulation,
+            history,
+            capacity: capacity.max(1),
+            cursor: 0,
+            stopped: false,
+        }
+    }
+
+    pub fn tick(&self) -> usize {
+        self.history[self.cursor].0
+    }
+
+    pub fn snapshot(&self) -> &str {
+        self.history[self.cursor].1.as_str()
+    }
+
+    /// True when showing the latest state, and the simulation has stopped changing.
+    pub fn stopped(&self) -> bool {
+        self.stopped && self.cursor + 1 == self.history.len()
+    }
+
+    /// Steps forward `n` ticks, or until the simulation stops. Returns the
+    /// number of ticks taken.
+    pub fn forward(&mut self, n: usize) -> usize {
+        let mut taken = 0;
+        while taken < n && self.step() {
+            taken += 1;
+        }
+        taken
+    }
+
+    /// Steps back `n` ticks, or as far as the history goes. Returns the
+    /// number of ticks taken.
+    pub fn back(&mut self, n: usize) -> usize {
+        let taken = n.min(self.cursor);
+        self.cursor -= taken;
+        taken
+    }
+
+    /// Steps forward until `condition` holds for the snapshot, the simulation
+    /// stops, or `RUN_LIMIT` ticks have passed. Returns whether the condition held.
+    pub fn run_until<C: Fn(&str) -> bool>(&mut self, condition: C) -> bool {
+        for _ in 0..RUN_LIMIT {
+            if !self.step() {
+                break;
+            }
+            if condition(self.snapshot()) {
+                return true;
+            }
+        }
+        false
+    }
+
+    fn step(&mut self) -> bool {
+        if self.cursor + 1 < self.history.len() {
+            self.cursor += 1;
+            return true;
+        }
+        if self.stopped || !self.simulation.step() {
+            self.stopped = true;
+            return false;
+        }
+        let tick = self.tick() + 1;
+        self.history.push_back((tick, self.simulation.snapshot()));
+        if self.history.len() > self.capacity {
+            self.history.pop_front();
+        }
+        self.cursor = self.history.len() - 1;
+        true
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Command {
+    Forward(usize),
+    Back(usize),
+    /// Runs until the snapshot contains the text, or until the simulation stops.
+    Until(Option<String>),
+    Help,
+    Quit,
+}
+
+pub const HELP: &str = "Commands:
+  s [n]      step forward n ticks (default 1); a blank line steps once
+  b [n]      step back n ticks (default 1), as far as the history goes
+  u [text]   run until the state contains text, or until it stops changing
+  h          show this help
+  q          quit";
+
+impl FromStr for Command {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let (command, argument) = match s.split_once(char::is_whitespace) {
+            Some((command, argument)) => (command, Some(argument.trim())),
+            None => (s, None),
+        };
+        let count = || -> anyhow::Result<usize> {
+            Ok(match argument {
+                Some(n) => n.parse()?,
+                None => 1,
+            })
+        };
+        Ok(match command {
+            "" | "s" => Self::Forward(count()?),
+            "b" => Self::Back(count()?),
+            "u" => Self::Until(argument.map(|a| a.to_owned())),
+            "h" | "?" => Self::Help,
+            "q" => Self::Quit,
+            _ => bail!("{s}: Unknown command (h for help)"),
+        })
+    }
+}
+
+/// Shows the stepper's state after each command read from `input`, until
+/// `q` or the end of `input`. `clear` redraws the terminal for each state.
+pub fn interact<R: BufRead, W: Write>(
+    stepper: &mut Stepper,
+    input: R,
+    output: &mut W,
+    clear: bool,
+) -> anyhow::Result<()> {
+    let mut message = String::from("h for help");
+    let mut lines = input.lines();
+    loop {
+        if clear {
+            write!(output, "\x1b[2J\x1b[H")?;
+        }
+        write!(output, "{}", stepper.snapshot())?;
+        let status = if stepper.stopped() { " (stopped)" } else { "" };
+        write!(output, "tick {}{status}: {message}\n> ", stepper.tick())?;
+        output.flush()?;
+        let line = match lines.next() {
+            Some(line) => line?,
+            None => return Ok(()),
+        };
+        message = match line.parse::<Command>() {
+            Ok(Command::Forward(n)) => format!("forward {}", stepper.forward(n)),
+            Ok(Command::Back(n)) => format!("back {}", stepper.back(n)),
+            Ok(Command::Until(text)) => {
+                let found = match &text {
+                    Some(text) => stepper.run_until(|s| s.contains(text.as_str())),
+                    None => stepper.run_until(|_| false),
+                };
+                match text {
+                    Some(text) if !found => format!("{text:?} not found"),
+                    _ => String::from("ran"),
+                }
+            }
+            Ok(Command::Help) => HELP.to_owned(),
+            Ok(Command::Quit) => return Ok(()),
+            Err(e) => e.to_string(),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        input::Input,
+        simulation::simulate_day,
+        stepper::{interact, Command, Stepper},
+    };
+
+    fn sand(capacity: usize) -> Stepper {
+        let input = Input::from_file("ex/day14.txt").unwrap();
+        Stepper::new(simulate_day(14, &input).unwrap(), capacity)
+    }
+
+    #[test]
+    fn test_rewind() {
+        let mut stepper = sand(4);
+        assert_eq!(stepper.forward(5), 5);
+        let fifth = stepper.snapshot().to_owned();
+        assert_eq!(stepper.back(10), 3);
+        assert_eq!(stepper.tick(), 2);
+        assert_eq!(stepper.forward(3), 3);
+        assert_eq!(stepper.snapshot(), fifth);
+        assert!(stepper.run_until(|s| s.matches('o').count() == 20));
+        assert_eq!(stepper.tick(), 20);
+        assert!(!stepper.run_until(|_| false));
+        assert_eq!((stepper.tick(), stepper.stopped()), (24, true));
+    }
+
+    #[test]
+    fn test_commands() {
+        assert_eq!("".parse::<Command>().unwrap(), Command::Forward(1));
+        assert_eq!("b 12".parse::<Command>().unwrap(), Command::Back(12));
+        assert_eq!(
+            "u ooo".parse::<Command>().unwrap(),
+            Command::Until(Some("ooo".to_owned()))
+        );
+        assert!("s many".parse::<Command>().is_err());
+
+        let mut stepper = sand(100);
+        let mut output = vec![];
+        interact(
+            &mut stepper,
+            "s 3\n\nb 2\nq\ns\n".as_bytes(),
+            &mut output,
+            false,
+        )
+        .unwrap();
+        assert_eq!(stepper.tick(), 2);
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("tick 4: forward 1"));
+    }
+}
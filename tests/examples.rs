@@ -0,0 +1,111 @@
+use std::{collections::BTreeMap, fs};
+
+use advent2022::{
+    input::Input,
+    runner::{solve_day, Part},
+};
+
+const MANIFEST: &str = "ex/answers.txt";
+
+fn day_of(input: &str) -> usize {
+    input
+        .trim_start_matches("day")
+        .chars()
+        .take_while(|c| c.is_ascii_digit())
+        .collect::<String>()
+        .parse()
+        .unwrap()
+}
+
+fn expected_answers() -> BTreeMap<String, Vec<(Part, String)>> {
+    let mut result: BTreeMap<String, Vec<(Part, String)>> = BTreeMap::new();
+    for (i, line) in fs::read_to_string(MANIFEST).unwrap().lines().enumerate() {
+        if line.starts_with('#') || line.trim().is_empty() {
+            continue;
+        }
+        let fields = line.split('\t').collect::<Vec<_>>();
+        assert_eq!(fields.len(), 3, "{MANIFEST}:{}: expected 3 fields", i + 1);
+        let part = fields[1].parse::<Part>().unwrap();
+        let answer = fields[2].replace("\\n", "\n");
+        result
+            .entry(fields[0].to_owned())
+            .or_default()
+            .push((part, answer));
+    }
+    result
+}
+
+fn check_day(day: usize) {
+    let expected = expected_answers();
+    let inputs = expected
+        .iter()
+        .filter(|(input, _)| day_of(input) == day)
+        .collect::<Vec<_>>();
+    assert!(!inputs.is_empty(), "No expected answers for day {day}");
+    for (input, answers) in inputs {
+        let parts = answers.iter().map(|(part, _)| *part).collect::<Vec<_>>();
+        let filename = format!("ex/{input}");
+        let input = Input::from_file(filename.as_str()).unwrap();
+        let result = solve_day(day, &input, &parts, false).unwrap();
+        assert_eq!(result.parts.len(), answers.len(), "{filename}");
+        for (actual, (part, answer)) in result.parts.iter().zip(answers.iter()) {
+            assert_eq!(actual.part, *part, "{filename}");
+            assert_eq!(actual.answer, *answer, "{filename} part {part}");
+        }
+    }
+}
+
+#[test]
+fn every_example_has_answers() {
+    let expected = expected_answers();
+    for entry in fs::read_dir("ex").unwrap() {
+        let name = entry.unwrap().file_name().into_string().unwrap();
+        if name.starts_with("day") {
+            assert!(expected.contains_key(&name), "{name} is not in {MANIFEST}");
+        }
+    }
+}
+
+macro_rules! example_tests {
+    ($($name:ident: $day:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                check_day($day);
+            }
+        )*
+    };
+}
+
+example_tests! {
+    day1: 1,
+    day2: 2,
+    day3: 3,
+    day4: 4,
+    day5: 5,
+    day6: 6,
+    day7: 7,
+    day8: 8,
+    day9: 9,
+    day10: 10,
+    day11: 11,
+    day12: 12,
+    day13: 13,
+    day14: 14,
+    day15: 15,
+    day16: 16,
+    day17: 17,
+    day18: 18,
+    day20: 20,
+    day21: 21,
+    day22: 22,
+    day23: 23,
+    day24: 24,
+    day25: 25,
+}
+
+#[test]
+#[ignore = "the blueprint search takes minutes on the example"]
+fn day19() {
+    check_day(19);
+}
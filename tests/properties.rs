@@ -0,0 +1,543 @@
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+
+use advent2022::{
+    day1::CalorieTally,
+    day17::Tetris,
+    day18::{self, Droplet},
+    day20::TrackedNums,
+    day21::MonkeyTroop,
+    day22::MonkeyNotes,
+    day23::{self, CellularElves},
+    day25::Snafu,
+    day3::{compartments, repair_rucksack, Rucksacks},
+    day4::{overlapping_pairs, Coverage, IntervalSet},
+    day5::{crane, CratePuzzle, Goal, Planner, Rearrangement},
+    day6::{markers, scanner},
+    detect::{check, detect},
+    generate::{generate, Rng},
+    input::Input,
+    runner::{solve_day, with_day, DayAction, Part},
+    Solution,
+};
+
+const SEEDS: u64 = 8;
+
+/// Days whose searches take too long to run over many random inputs; day 15
+/// always searches the full-size area.
+const SLOW_DAYS: [usize; 3] = [15, 16, 19];
+
+fn generated(day: usize, size: usize, seed: u64) -> Input {
+    let name = format!("generated day {day}, size {size}, seed {seed}");
+    Input::new(name.as_str(), generate(day, size, seed).unwrap().as_str())
+}
+
+struct Load<'a>(&'a Input);
+
+impl DayAction for Load<'_> {
+    type Output = anyhow::Result<()>;
+
+    fn act<S: Solution>(self) -> Self::Output {
+        S::load(self.0).map(|_| ())
+    }
+}
+
+#[test]
+fn generated_inputs_parse() {
+    for day in 1..=25 {
+        for seed in 0..SEEDS {
+            for size in [1, 10, 50] {
+                let input = generated(day, size, seed);
+                if let Err(e) = with_day(day, Load(&input)).unwrap() {
+                    panic!("{}: {e}\n{}", input.name(), input.text());
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn generated_inputs_solve() {
+    for day in (1..=25).filter(|d| !SLOW_DAYS.contains(d)) {
+        for seed in 0..SEEDS / 2 {
+            let input = generated(day, 12, seed);
+            if let Err(e) = solve_day(day, &input, &Part::both(), false) {
+                panic!("{}: {e}\n{}", input.name(), input.text());
+            }
+        }
+    }
+}
+
+#[test]
+fn generated_inputs_are_detected() {
+    for day in 1..=25 {
+        for seed in 0..SEEDS {
+            for size in [12, 50] {
+                let input = generated(day, size, seed);
+                let guesses = detect(&input);
+                assert_eq!(guesses[0].day, day, "{guesses:?}\n{}", input.text());
+                assert!(check(day, &input).is_ok());
+            }
+        }
+    }
+}
+
+#[test]
+fn tally_matches_sorting() {
+    let mut rng = Rng::new(1);
+    for seed in 0..SEEDS {
+        let text = generate(1, 200, seed).unwrap();
+        let mut totals = text
+            .split("\n\n")
+            .map(|elf| elf.lines().map(|l| l.parse::<i64>().unwrap()).sum::<i64>())
+            .enumerate()
+            .collect::<Vec<_>>();
+        totals.sort_by_key(|(elf, total)| (-total, *elf));
+        let k = 1 + rng.below(totals.len());
+        let tally = CalorieTally::from_input(&Input::from(text.as_str()), k).unwrap();
+        assert_eq!(tally.top(), totals[..k], "{text}");
+    }
+}
+
+#[test]
+fn repairs_keep_badges() {
+    for seed in 0..SEEDS {
+        let text = generate(3, 30, seed).unwrap();
+        let rucksacks = Rucksacks::from_input(&Input::from(text.as_str())).unwrap();
+        let plan = rucksacks.repair(3).unwrap();
+        let repaired = Rucksacks::from_input(&Input::from(plan.lines.join("\n").as_str())).unwrap();
+        assert_eq!(repaired.badges(3).unwrap(), rucksacks.badges(3).unwrap());
+        for (before, after) in text.lines().zip(plan.lines.iter()) {
+            let (left, right) = compartments(after.as_str()).unwrap();
+            assert!(left.intersection(right).is_empty(), "{before} -> {after}");
+            let sorted = |s: &str| {
+                let mut chars = s.chars().collect::<Vec<_>>();
+                chars.sort();
+                chars
+            };
+            if plan.transfers.is_empty() {
+                assert_eq!(sorted(before), sorted(after));
+            }
+        }
+    }
+}
+
+/// The fewest items that change compartment, trying every set of types for the left.
+fn fewest_moves(line: &str) -> Option<usize> {
+    let (left, right) = line.split_at(line.len() / 2);
+    let mut types = line.chars().collect::<Vec<_>>();
+    types.sort();
+    types.dedup();
+    (0..1u32 << types.len())
+        .filter_map(|chosen| {
+            let on_left = |c: char| chosen & 1 << types.iter().position(|t| *t == c).unwrap() != 0;
+            let size = line.chars().filter(|c| on_left(*c)).count();
+            (size == left.len()).then(|| {
+                left.chars().filter(|c| !on_left(*c)).count()
+                    + right.chars().filter(|c| on_left(*c)).count()
+            })
+        })
+        .min()
+}
+
+#[test]
+fn repair_is_minimal() {
+    let mut rng = Rng::new(3);
+    for _ in 0..200 {
+        let len = 2 * (1 + rng.below(6));
+        let line = (0..len)
+            .map(|_| *rng.pick(&['a', 'b', 'c', 'd', 'e', 'F']))
+            .collect::<String>();
+        let repair = repair_rucksack(line.as_str()).unwrap();
+        assert_eq!(
+            repair.as_ref().map(|(_, moves)| *moves),
+            fewest_moves(line.as_str()),
+            "{line}"
+        );
+    }
+}
+
+fn random_ranges(rng: &mut Rng, n: usize) -> Vec<std::ops::RangeInclusive<i64>> {
+    (0..n)
+        .map(|_| {
+            let start = rng.range(0, 40);
+            start..=start + rng.range(-1, 8)
+        })
+        .collect()
+}
+
+#[test]
+fn interval_sets_match_section_sets() {
+    let mut rng = Rng::new(4);
+    for _ in 0..200 {
+        let ranges = random_ranges(&mut rng, 12);
+        let (left, right) = ranges.split_at(rng.below(ranges.len()));
+        let sections = |ranges: &[std::ops::RangeInclusive<i64>]| {
+            ranges.iter().cloned().flatten().collect::<BTreeSet<_>>()
+        };
+        let expanded = |set: &IntervalSet| {
+            set.ranges()
+                .iter()
+                .cloned()
+                .flatten()
+                .collect::<BTreeSet<_>>()
+        };
+        let (a, b) = (
+            left.iter().cloned().collect::<IntervalSet>(),
+            right.iter().cloned().collect::<IntervalSet>(),
+        );
+        let (sa, sb) = (sections(left), sections(right));
+        assert_eq!(expanded(&a), sa);
+        assert_eq!(a.len(), sa.len() as i64);
+        assert_eq!(expanded(&a.union(&b)), &sa | &sb);
+        assert_eq!(expanded(&a.intersection(&b)), &sa & &sb);
+        assert_eq!(expanded(&a.difference(&b)), &sa - &sb);
+        assert!(a
+            .union(&b)
+            .ranges()
+            .windows(2)
+            .all(|w| w[0].end() + 1 < *w[1].start()));
+
+        let coverage = Coverage::new(&ranges);
+        let mut counts = BTreeMap::new();
+        for section in ranges.iter().cloned().flatten() {
+            *counts.entry(section).or_insert(0) += 1;
+        }
+        let triple = counts.iter().filter(|(_, c)| **c >= 3).map(|(s, _)| *s);
+        assert_eq!(expanded(&coverage.at_least(3)), triple.collect());
+
+        let mut pairs = vec![];
+        for i in 0..ranges.len() {
+            for j in i + 1..ranges.len() {
+                if sections(&ranges[i..=i])
+                    .intersection(&sections(&ranges[j..=j]))
+                    .next()
+                    .is_some()
+                {
+                    pairs.push((i, j));
+                }
+            }
+        }
+        assert_eq!(overlapping_pairs(&ranges), pairs);
+    }
+}
+
+#[test]
+fn crate_drawings_round_trip() {
+    for seed in 0..SEEDS {
+        let input = generated(5, 10, seed);
+        let puzzle = CratePuzzle::from_input(&input).unwrap();
+        let drawing = puzzle.state.to_string();
+        assert!(input.text().starts_with(drawing.as_str()), "{drawing}");
+    }
+}
+
+#[test]
+fn crate_moves_undo() {
+    for seed in 0..SEEDS {
+        let input = generated(5, 10, seed);
+        let puzzle = CratePuzzle::from_input(&input).unwrap();
+        for name in ["9000", "9001", "lift:1", "lift:3"] {
+            let crane = crane(name).unwrap();
+            let inverse = puzzle.inverse(crane.as_ref()).unwrap();
+            assert_eq!(inverse.run(crane.as_ref()).unwrap(), puzzle.state, "{name}");
+
+            let mut rearrangement = Rearrangement::new(&puzzle, crane.as_ref());
+            let middle = rearrangement.seek(puzzle.script.len() / 2).unwrap().clone();
+            rearrangement.seek(puzzle.script.len()).unwrap();
+            assert_eq!(
+                rearrangement.seek(puzzle.script.len() / 2).unwrap(),
+                &middle
+            );
+            assert_eq!(rearrangement.seek(0).unwrap(), &puzzle.state);
+        }
+    }
+}
+
+#[test]
+fn plans_are_no_longer_than_scripts() {
+    for seed in 0..SEEDS {
+        let puzzle = CratePuzzle::from_input(&generated(5, 1, seed)).unwrap();
+        for name in ["9000", "9001"] {
+            let crane = crane(name).unwrap();
+            let finish = puzzle.run(crane.as_ref()).unwrap();
+            for goal in [Goal::Arrangement(finish.clone()), Goal::Tops(finish.tops())] {
+                let script = Planner::default()
+                    .plan(&puzzle.state, &goal, crane.as_ref())
+                    .unwrap();
+                assert!(script.len() <= puzzle.script.len(), "{goal:?}");
+                let plan = CratePuzzle::new("plan", puzzle.state.clone(), script);
+                assert!(goal.reached(&plan.run(crane.as_ref()).unwrap()));
+            }
+        }
+    }
+}
+
+#[test]
+fn marker_scanner_matches_windows() {
+    for seed in 0..SEEDS {
+        let line = generate(6, 200, seed).unwrap();
+        let chars = line.chars().collect::<Vec<_>>();
+        let found = markers(line.bytes(), &[4, 14]);
+        for target_len in [4, 14] {
+            let expected = chars
+                .windows(target_len)
+                .enumerate()
+                .filter(|(_, w)| w.iter().collect::<BTreeSet<_>>().len() == target_len)
+                .map(|(i, _)| i + target_len)
+                .collect::<Vec<_>>();
+            assert_eq!(
+                scanner(line.as_str(), target_len),
+                Some(expected[0]),
+                "{line}"
+            );
+            let positions = found
+                .iter()
+                .filter(|m| m.length == target_len)
+                .map(|m| m.position)
+                .collect::<Vec<_>>();
+            assert_eq!(positions, expected, "{line}");
+        }
+    }
+}
+
+#[test]
+fn tower_repeat_matches_simulation() {
+    let mut rng = Rng::new(17);
+    for seed in 0..SEEDS {
+        let jets = generate(17, 1 + rng.below(40), seed).unwrap();
+        let cycle = Tetris::find_cycle(jets.as_str()).unwrap();
+        let first = cycle.start + cycle.length;
+        for _ in 0..3 {
+            let drops = first + rng.below(3000);
+            assert_eq!(
+                cycle.metric_at(drops) as isize,
+                Tetris::limit_solver(jets.as_str(), drops as isize).unwrap(),
+                "{jets} after {drops} drops"
+            );
+        }
+    }
+}
+
+fn surface_area(cubes: &BTreeSet<(i64, i64, i64)>) -> usize {
+    cubes
+        .iter()
+        .flat_map(|c| neighbors(*c))
+        .filter(|n| !cubes.contains(n))
+        .count()
+}
+
+fn neighbors((x, y, z): (i64, i64, i64)) -> [(i64, i64, i64); 6] {
+    [
+        (x - 1, y, z),
+        (x + 1, y, z),
+        (x, y - 1, z),
+        (x, y + 1, z),
+        (x, y, z - 1),
+        (x, y, z + 1),
+    ]
+}
+
+/// Floods the air around the droplet, within a box one larger than it.
+fn exterior_area(cubes: &BTreeSet<(i64, i64, i64)>) -> usize {
+    let lo = cubes
+        .iter()
+        .map(|(x, y, z)| *x.min(y).min(z))
+        .min()
+        .unwrap()
+        - 1;
+    let hi = cubes
+        .iter()
+        .map(|(x, y, z)| *x.max(y).max(z))
+        .max()
+        .unwrap()
+        + 1;
+    let inside = |(x, y, z): (i64, i64, i64)| [x, y, z].iter().all(|v| (lo..=hi).contains(v));
+    let mut outside = BTreeSet::new();
+    let mut queue = VecDeque::from([(lo, lo, lo)]);
+    outside.insert((lo, lo, lo));
+    let mut area = 0;
+    while let Some(air) = queue.pop_front() {
+        for n in neighbors(air) {
+            if cubes.contains(&n) {
+                area += 1;
+            } else if inside(n) && outside.insert(n) {
+                queue.push_back(n);
+            }
+        }
+    }
+    area
+}
+
+#[test]
+fn droplet_matches_face_count() {
+    for seed in 0..SEEDS {
+        let text = generate(18, 60, seed).unwrap();
+        let cubes = text
+            .lines()
+            .map(|line| {
+                let c = line
+                    .split(',')
+                    .map(|n| n.parse().unwrap())
+                    .collect::<Vec<_>>();
+                (c[0], c[1], c[2])
+            })
+            .collect::<BTreeSet<_>>();
+        let droplet = Droplet::from_input(&Input::from(text.as_str())).unwrap();
+        assert_eq!(day18::part1(&droplet), surface_area(&cubes), "{text}");
+        assert_eq!(day18::part2(&droplet), exterior_area(&cubes), "{text}");
+    }
+}
+
+/// Mixes by removing and reinserting each number in a plain `Vec`.
+fn mix_reference(nums: &[i64]) -> Vec<i64> {
+    let mut mixed = nums.iter().copied().enumerate().collect::<Vec<_>>();
+    let len = mixed.len() as i64;
+    for original in 0..nums.len() {
+        let from = mixed.iter().position(|(i, _)| *i == original).unwrap();
+        let entry = mixed.remove(from);
+        let to = (from as i64 + entry.1).rem_euclid(len - 1);
+        mixed.insert(to as usize, entry);
+    }
+    mixed.iter().map(|(_, n)| *n).collect()
+}
+
+/// Rotates a circular list to start at its zero.
+fn from_zero(nums: &[i64]) -> Vec<i64> {
+    let zero = nums.iter().position(|n| *n == 0).unwrap();
+    nums[zero..]
+        .iter()
+        .chain(nums[..zero].iter())
+        .copied()
+        .collect()
+}
+
+#[test]
+fn mix_matches_reference() {
+    for seed in 0..SEEDS {
+        for size in [3, 7, 40] {
+            let text = generate(20, size, seed).unwrap();
+            let mut nums = TrackedNums::from_input(&Input::from(text.as_str())).unwrap();
+            let expected = mix_reference(nums.nums().as_slice());
+            nums.mix();
+            assert_eq!(from_zero(&nums.nums()), from_zero(&expected), "{text}");
+        }
+    }
+}
+
+#[test]
+fn humn_shouts_the_part_2_answer() {
+    for seed in 0..SEEDS {
+        let text = generate(21, 40, seed).unwrap();
+        let humn = text
+            .lines()
+            .find_map(|line| line.strip_prefix("humn: "))
+            .unwrap()
+            .parse::<i64>()
+            .unwrap();
+        let troop = MonkeyTroop::from_input(&Input::from(text.as_str())).unwrap();
+        assert_eq!(troop.part2().unwrap(), humn, "{text}");
+    }
+}
+
+/// Without walls, walking four faces straight on returns to the same tile.
+#[test]
+fn cube_walks_return_to_start() {
+    for seed in 0..SEEDS {
+        let text = generate(22, 30, seed).unwrap().replace('#', ".");
+        let board = text
+            .lines()
+            .take_while(|l| !l.is_empty())
+            .collect::<Vec<_>>();
+        let width = board.iter().map(|l| l.len()).max().unwrap();
+        let lap = 4 * board.len().min(width) / 3;
+        let start = board[0].find('.').unwrap();
+        let text = format!("{}\n\n{lap}R{lap}R{lap}L{lap}", board.join("\n"));
+        let notes = MonkeyNotes::load(&Input::from(text.as_str())).unwrap();
+        let facing_down = 1;
+        let expected = 1000 + 4 * (start as isize + 1) + facing_down;
+        assert_eq!(notes.part2().unwrap(), expected, "{text}");
+    }
+}
+
+type Elf = (i64, i64);
+
+/// One round of the elves' rules, on a plain set of positions. Returns
+/// whether any elf moved.
+fn elf_round(elves: &mut BTreeSet<Elf>, round: usize) -> bool {
+    let checks: [(Elf, [Elf; 3]); 4] = [
+        ((0, -1), [(-1, -1), (0, -1), (1, -1)]),
+        ((0, 1), [(-1, 1), (0, 1), (1, 1)]),
+        ((-1, 0), [(-1, -1), (-1, 0), (-1, 1)]),
+        ((1, 0), [(1, -1), (1, 0), (1, 1)]),
+    ];
+    let occupied = |(x, y): Elf, (dx, dy): Elf| elves.contains(&(x + dx, y + dy));
+    let mut proposals: BTreeMap<Elf, Vec<Elf>> = BTreeMap::new();
+    for elf in elves.iter() {
+        let alone = (-1..=1)
+            .flat_map(|dx| (-1..=1).map(move |dy| (dx, dy)))
+            .filter(|d| *d != (0, 0))
+            .all(|d| !occupied(*elf, d));
+        if alone {
+            continue;
+        }
+        for i in 0..checks.len() {
+            let (step, cells) = checks[(round + i) % checks.len()];
+            if cells.iter().all(|d| !occupied(*elf, *d)) {
+                let target = (elf.0 + step.0, elf.1 + step.1);
+                proposals.entry(target).or_default().push(*elf);
+                break;
+            }
+        }
+    }
+    let mut moved = false;
+    for (target, proposers) in proposals {
+        if proposers.len() == 1 {
+            elves.remove(&proposers[0]);
+            elves.insert(target);
+            moved = true;
+        }
+    }
+    moved
+}
+
+#[test]
+fn elves_match_reference() {
+    for seed in 0..SEEDS {
+        let text = generate(23, 12, seed).unwrap();
+        let mut elves = BTreeSet::new();
+        for (y, line) in text.lines().enumerate() {
+            for (x, c) in line.chars().enumerate() {
+                if c == '#' {
+                    elves.insert((x as i64, y as i64));
+                }
+            }
+        }
+        let mut round = 0;
+        while round < 10 {
+            elf_round(&mut elves, round);
+            round += 1;
+        }
+        let width =
+            elves.iter().map(|e| e.0).max().unwrap() - elves.iter().map(|e| e.0).min().unwrap() + 1;
+        let height =
+            elves.iter().map(|e| e.1).max().unwrap() - elves.iter().map(|e| e.1).min().unwrap() + 1;
+        let empty = (width * height) as usize - elves.len();
+        while elf_round(&mut elves, round) {
+            round += 1;
+        }
+
+        let cellular = CellularElves::from_input(&Input::from(text.as_str())).unwrap();
+        assert_eq!(day23::part1(&cellular), empty, "{text}");
+        assert_eq!(day23::part2(&cellular), round + 1, "{text}");
+    }
+}
+
+#[test]
+fn snafu_round_trips() {
+    let mut rng = Rng::new(25);
+    for _ in 0..1000 {
+        let value = rng.range(1, i64::MAX / 4);
+        let snafu = Snafu(value).to_string();
+        assert_eq!(snafu.parse::<Snafu>(), Ok(Snafu(value)), "{snafu}");
+    }
+}